@@ -0,0 +1,74 @@
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio_stream::Stream;
+
+/// Token usage reported by a provider for one completion.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
+/// A single function/tool invocation requested by the model, normalized across
+/// providers. `arguments` is the raw JSON-encoded argument object as returned by the
+/// provider — callers parse it against the tool's declared schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: String,
+}
+
+/// Result of a non-streaming [`LlmProvider::complete`] call.
+#[derive(Debug, Clone, Default)]
+pub struct CompletionResponse {
+    pub text: Option<String>,
+    pub tool_calls: Vec<ToolCall>,
+    pub usage: Usage,
+}
+
+/// One increment of a streamed completion.
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// A chunk of assistant text to append.
+    Delta(String),
+    /// The model requested one or more tool calls, fully assembled from streamed
+    /// fragments.
+    ToolCalls(Vec<ToolCall>),
+    /// A single tool call, finalized from a provider that streams one block at a time
+    /// (e.g. Anthropic's `tool_use` content blocks) rather than batching them.
+    ToolCall { id: String, name: String, arguments: String },
+    /// Stream is complete.
+    Done(Usage),
+    /// An error occurred.
+    Error(String),
+}
+
+/// A chat completion backend. `messages` and `tools` are passed as raw JSON; each
+/// `tools` entry uses the shared `{name, description, parameters}` shape, which every
+/// provider translates to its own API's tool format.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Provider identifier (e.g. "openai", "anthropic").
+    fn name(&self) -> &str;
+
+    /// Model identifier this provider instance talks to.
+    fn id(&self) -> &str;
+
+    /// Run one completion, optionally advertising `tools` the model may call.
+    async fn complete(
+        &self,
+        messages: &[serde_json::Value],
+        tools: &[serde_json::Value],
+    ) -> anyhow::Result<CompletionResponse>;
+
+    /// Stream a completion as incremental [`StreamEvent`]s, optionally advertising
+    /// `tools` the model may call mid-stream.
+    fn stream(
+        &self,
+        messages: Vec<serde_json::Value>,
+        tools: &[serde_json::Value],
+    ) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send + '_>>;
+}
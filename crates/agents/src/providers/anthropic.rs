@@ -4,22 +4,62 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use tokio_stream::Stream;
 
-use crate::model::{CompletionResponse, LlmProvider, StreamEvent, Usage};
+use crate::model::{CompletionResponse, LlmProvider, StreamEvent, ToolCall, Usage};
+use crate::providers::HttpOptions;
+
+/// Translate one of our internal `{name, description, parameters}` tool definitions
+/// into Anthropic's `{name, description, input_schema}` shape.
+fn to_anthropic_tool(tool: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "name": tool["name"],
+        "description": tool["description"],
+        "input_schema": tool["parameters"],
+    })
+}
+
+/// A `tool_use` block being assembled from streamed `content_block_start` /
+/// `input_json_delta` events, keyed by its content-block index.
+struct StreamingToolUse {
+    id: String,
+    name: String,
+    json_buf: String,
+}
+
+/// Parse one `tool_use` content block into a [`ToolCall`].
+fn parse_tool_use(block: &serde_json::Value) -> Option<ToolCall> {
+    Some(ToolCall {
+        id: block["id"].as_str()?.to_string(),
+        name: block["name"].as_str()?.to_string(),
+        arguments: serde_json::to_string(&block["input"]).unwrap_or_else(|_| "{}".to_string()),
+    })
+}
 
 pub struct AnthropicProvider {
     api_key: String,
     model: String,
     base_url: String,
+    max_tokens: u32,
     client: reqwest::Client,
 }
 
 impl AnthropicProvider {
-    pub fn new(api_key: String, model: String, base_url: String) -> Self {
+    pub fn new(api_key: String, model: String, base_url: String, max_tokens: u32) -> Self {
+        Self::with_http_options(api_key, model, base_url, max_tokens, HttpOptions::default())
+    }
+
+    pub fn with_http_options(
+        api_key: String,
+        model: String,
+        base_url: String,
+        max_tokens: u32,
+        http: HttpOptions,
+    ) -> Self {
         Self {
             api_key,
             model,
             base_url,
-            client: reqwest::Client::new(),
+            max_tokens,
+            client: http.build_client().unwrap_or_else(|_| reqwest::Client::new()),
         }
     }
 }
@@ -37,14 +77,18 @@ impl LlmProvider for AnthropicProvider {
     async fn complete(
         &self,
         messages: &[serde_json::Value],
-        _tools: &[serde_json::Value],
+        tools: &[serde_json::Value],
     ) -> anyhow::Result<CompletionResponse> {
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
-            "max_tokens": 4096,
+            "max_tokens": self.max_tokens,
             "messages": messages,
         });
 
+        if !tools.is_empty() {
+            body["tools"] = serde_json::Value::Array(tools.iter().map(to_anthropic_tool).collect());
+        }
+
         let resp = self
             .client
             .post(format!("{}/v1/messages", self.base_url))
@@ -72,6 +116,16 @@ impl LlmProvider for AnthropicProvider {
                     .reduce(|a, b| a + &b)
             });
 
+        let tool_calls = resp["content"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter(|b| b["type"].as_str() == Some("tool_use"))
+                    .filter_map(parse_tool_use)
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let usage = Usage {
             input_tokens: resp["usage"]["input_tokens"].as_u64().unwrap_or(0) as u32,
             output_tokens: resp["usage"]["output_tokens"].as_u64().unwrap_or(0) as u32,
@@ -79,7 +133,7 @@ impl LlmProvider for AnthropicProvider {
 
         Ok(CompletionResponse {
             text,
-            tool_calls: vec![],
+            tool_calls,
             usage,
         })
     }
@@ -88,15 +142,21 @@ impl LlmProvider for AnthropicProvider {
     fn stream(
         &self,
         messages: Vec<serde_json::Value>,
+        tools: &[serde_json::Value],
     ) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send + '_>> {
+        let tools = tools.to_vec();
         Box::pin(async_stream::stream! {
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "model": self.model,
-                "max_tokens": 4096,
+                "max_tokens": self.max_tokens,
                 "messages": messages,
                 "stream": true,
             });
 
+            if !tools.is_empty() {
+                body["tools"] = serde_json::Value::Array(tools.iter().map(to_anthropic_tool).collect());
+            }
+
             let resp = match self
                 .client
                 .post(format!("{}/v1/messages", self.base_url))
@@ -126,6 +186,10 @@ impl LlmProvider for AnthropicProvider {
             let mut buf = String::new();
             let mut input_tokens: u32 = 0;
             let mut output_tokens: u32 = 0;
+            // `tool_use` blocks stream as a `content_block_start` carrying the id/name,
+            // followed by zero or more `input_json_delta`s whose `partial_json`
+            // fragments must be concatenated (in order) before the buffer is valid JSON.
+            let mut tool_blocks: std::collections::HashMap<u64, StreamingToolUse> = std::collections::HashMap::new();
 
             while let Some(chunk) = byte_stream.next().await {
                 let chunk = match chunk {
@@ -146,12 +210,46 @@ impl LlmProvider for AnthropicProvider {
                             if let Ok(evt) = serde_json::from_str::<serde_json::Value>(data) {
                                 let evt_type = evt["type"].as_str().unwrap_or("");
                                 match evt_type {
+                                    "content_block_start" => {
+                                        let index = evt["index"].as_u64().unwrap_or(0);
+                                        let block = &evt["content_block"];
+                                        if block["type"].as_str() == Some("tool_use") {
+                                            tool_blocks.insert(index, StreamingToolUse {
+                                                id: block["id"].as_str().unwrap_or_default().to_string(),
+                                                name: block["name"].as_str().unwrap_or_default().to_string(),
+                                                json_buf: String::new(),
+                                            });
+                                        }
+                                    }
                                     "content_block_delta" => {
                                         if let Some(text) = evt["delta"]["text"].as_str() {
                                             if !text.is_empty() {
                                                 yield StreamEvent::Delta(text.to_string());
                                             }
                                         }
+                                        if let Some(partial) = evt["delta"]["partial_json"].as_str() {
+                                            let index = evt["index"].as_u64().unwrap_or(0);
+                                            if let Some(entry) = tool_blocks.get_mut(&index) {
+                                                entry.json_buf.push_str(partial);
+                                            }
+                                        }
+                                    }
+                                    "content_block_stop" => {
+                                        let index = evt["index"].as_u64().unwrap_or(0);
+                                        if let Some(entry) = tool_blocks.remove(&index) {
+                                            let arguments = if entry.json_buf.trim().is_empty() {
+                                                "{}".to_string()
+                                            } else {
+                                                entry.json_buf
+                                            };
+                                            if serde_json::from_str::<serde_json::Value>(&arguments).is_ok() {
+                                                yield StreamEvent::ToolCall {
+                                                    id: entry.id,
+                                                    name: entry.name,
+                                                    arguments,
+                                                };
+                                            }
+                                        }
                                     }
                                     "message_delta" => {
                                         if let Some(u) = evt["usage"]["output_tokens"].as_u64() {
@@ -0,0 +1,184 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+
+use super::{DEFAULT_MAX_TOKENS, HttpOptions, ModelInfo, ProviderRegistry};
+use crate::model::LlmProvider;
+use crate::providers::{anthropic::AnthropicProvider, openai::OpenAiProvider};
+
+/// Declarative provider config file consumed by [`ProviderRegistry::from_config`].
+/// `version` lets the format evolve without breaking files written against an older
+/// schema; only version 1 is currently understood.
+#[derive(Debug, serde::Deserialize)]
+pub struct ProviderConfigFile {
+    pub version: u32,
+    #[serde(default)]
+    pub providers: Vec<ProviderConfigEntry>,
+}
+
+/// One declared model/client. Lets a user register a model this crate has never heard
+/// of (a freshly released one, or a self-hosted endpoint) without a code change.
+#[derive(Debug, serde::Deserialize)]
+pub struct ProviderConfigEntry {
+    /// Backend to talk to: "anthropic" or "openai".
+    pub provider: String,
+    pub model: String,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Friendly name shown in `list_models`; defaults to `model`.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Proxy URL for this provider's HTTP client. Falls back to the standard
+    /// `HTTPS_PROXY`/`ALL_PROXY` env vars when unset.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Connection timeout in seconds.
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// Overall request timeout in seconds.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl ProviderConfigEntry {
+    fn http_options(&self) -> HttpOptions {
+        let mut opts = HttpOptions::from_env();
+        if self.proxy.is_some() {
+            opts.proxy = self.proxy.clone();
+        }
+        opts.connect_timeout = self.connect_timeout_secs.map(std::time::Duration::from_secs);
+        opts.timeout = self.timeout_secs.map(std::time::Duration::from_secs);
+        opts
+    }
+}
+
+impl ProviderConfigEntry {
+    fn resolve_api_key(&self) -> anyhow::Result<String> {
+        if let Some(key) = &self.api_key {
+            return Ok(key.clone());
+        }
+        if let Some(env_var) = &self.api_key_env {
+            return std::env::var(env_var).with_context(|| format!("env var '{env_var}' is not set"));
+        }
+        bail!(
+            "provider entry for model '{}' has neither `api_key` nor `api_key_env`",
+            self.model
+        );
+    }
+}
+
+impl ProviderRegistry {
+    /// Load a declarative provider config file — TOML or YAML, chosen by file
+    /// extension — and merge its entries into a registry seeded from [`Self::from_env`].
+    /// Config entries take priority over env-discovered providers for the same model
+    /// id, so a declared model can override a built-in's `base_url`/`max_tokens`, or
+    /// register a model entirely unknown to this crate.
+    pub fn from_config(path: &Path) -> anyhow::Result<Self> {
+        let mut reg = Self::from_env();
+
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading provider config '{}'", path.display()))?;
+
+        let config: ProviderConfigFile = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&raw).context("invalid TOML provider config")?,
+            _ => serde_yaml::from_str(&raw).context("invalid YAML provider config")?,
+        };
+
+        if config.version != 1 {
+            bail!("unsupported provider config version {} (expected 1)", config.version);
+        }
+
+        for entry in &config.providers {
+            reg.register_config_entry(entry)?;
+        }
+
+        Ok(reg)
+    }
+
+    fn register_config_entry(&mut self, entry: &ProviderConfigEntry) -> anyhow::Result<()> {
+        let api_key = entry.resolve_api_key()?;
+        let max_tokens = entry.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        let display_name = entry.display_name.clone().unwrap_or_else(|| entry.model.clone());
+        let http = entry.http_options();
+
+        let provider: Arc<dyn LlmProvider> = match entry.provider.as_str() {
+            "anthropic" => {
+                let base_url = entry
+                    .base_url
+                    .clone()
+                    .unwrap_or_else(|| "https://api.anthropic.com".into());
+                Arc::new(AnthropicProvider::with_http_options(
+                    api_key,
+                    entry.model.clone(),
+                    base_url,
+                    max_tokens,
+                    http,
+                ))
+            },
+            "openai" => {
+                let base_url = entry
+                    .base_url
+                    .clone()
+                    .unwrap_or_else(|| "https://api.openai.com/v1".into());
+                Arc::new(OpenAiProvider::with_http_options(api_key, entry.model.clone(), base_url, http))
+            },
+            other => bail!(
+                "unsupported provider '{other}' in config entry for model '{}'",
+                entry.model
+            ),
+        };
+
+        self.register(
+            ModelInfo {
+                id: entry.model.clone(),
+                provider: entry.provider.clone(),
+                display_name,
+                max_tokens,
+            },
+            provider,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let path = std::env::temp_dir().join(format!(
+            "moltis-provider-config-v2-{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "version: 2\nproviders: []\n").unwrap();
+        let result = ProviderRegistry::from_config(&path);
+        let _ = std::fs::remove_file(&path);
+        let err = result.err().expect("version 2 config should be rejected");
+        assert!(err.to_string().contains("unsupported provider config version"));
+    }
+
+    #[test]
+    fn entry_requires_an_api_key_source() {
+        let entry = ProviderConfigEntry {
+            provider: "openai".into(),
+            model: "gpt-4o".into(),
+            max_tokens: None,
+            base_url: None,
+            api_key: None,
+            api_key_env: None,
+            display_name: None,
+            proxy: None,
+            connect_timeout_secs: None,
+            timeout_secs: None,
+        };
+        assert!(entry.resolve_api_key().is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio_stream::Stream;
+
+use crate::model::{CompletionResponse, LlmProvider, StreamEvent};
+
+/// Wraps an ordered chain of providers and transparently advances to the next one on a
+/// failure, so a transient 429/5xx or network error from one backend doesn't abort the
+/// whole completion. Typically built via [`super::ProviderRegistry::with_fallback`].
+pub struct FallbackProvider {
+    providers: Vec<Arc<dyn LlmProvider>>,
+}
+
+impl FallbackProvider {
+    /// `providers` must be non-empty; earlier entries are preferred, later ones are
+    /// only tried after an earlier one fails.
+    pub fn new(providers: Vec<Arc<dyn LlmProvider>>) -> Self {
+        assert!(!providers.is_empty(), "FallbackProvider needs at least one provider");
+        Self { providers }
+    }
+}
+
+#[async_trait]
+impl LlmProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        self.providers[0].name()
+    }
+
+    fn id(&self) -> &str {
+        self.providers[0].id()
+    }
+
+    async fn complete(
+        &self,
+        messages: &[serde_json::Value],
+        tools: &[serde_json::Value],
+    ) -> anyhow::Result<CompletionResponse> {
+        let mut last_err = None;
+        for provider in &self.providers {
+            match provider.complete(messages, tools).await {
+                Ok(resp) => return Ok(resp),
+                Err(e) => {
+                    tracing::warn!(provider = provider.name(), error = %e, "provider failed, trying next in fallback chain");
+                    last_err = Some(e);
+                },
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("no providers configured")))
+    }
+
+    fn stream(
+        &self,
+        messages: Vec<serde_json::Value>,
+        tools: &[serde_json::Value],
+    ) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send + '_>> {
+        Box::pin(async_stream::stream! {
+            let last = self.providers.len() - 1;
+            for (i, provider) in self.providers.iter().enumerate() {
+                let mut inner = provider.stream(messages.clone(), tools);
+                let mut emitted_delta = false;
+                let mut failed_before_output = false;
+
+                while let Some(event) = inner.next().await {
+                    match event {
+                        StreamEvent::Delta(text) => {
+                            emitted_delta = true;
+                            yield StreamEvent::Delta(text);
+                        }
+                        StreamEvent::Error(e) if !emitted_delta && i < last => {
+                            tracing::warn!(provider = provider.name(), error = %e, "provider stream failed before any output, trying next in fallback chain");
+                            failed_before_output = true;
+                            break;
+                        }
+                        other @ (StreamEvent::Done(_) | StreamEvent::Error(_)) => {
+                            yield other;
+                            return;
+                        }
+                        other => yield other,
+                    }
+                }
+
+                if !failed_before_output {
+                    return;
+                }
+            }
+        })
+    }
+}
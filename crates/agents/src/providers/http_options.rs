@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// HTTP client tuning shared by every provider: an optional proxy to route through, and
+/// timeouts to fail fast instead of hanging on a dead or slow endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct HttpOptions {
+    pub proxy: Option<String>,
+    pub connect_timeout: Option<Duration>,
+    pub timeout: Option<Duration>,
+}
+
+impl HttpOptions {
+    /// Fall back to the standard `HTTPS_PROXY`/`ALL_PROXY` env vars when no proxy was
+    /// configured explicitly.
+    pub fn from_env() -> Self {
+        let proxy = std::env::var("HTTPS_PROXY")
+            .or_else(|_| std::env::var("https_proxy"))
+            .or_else(|_| std::env::var("ALL_PROXY"))
+            .or_else(|_| std::env::var("all_proxy"))
+            .ok();
+        Self {
+            proxy,
+            connect_timeout: None,
+            timeout: None,
+        }
+    }
+
+    /// Build a [`reqwest::Client`] applying the configured proxy and timeouts.
+    pub fn build_client(&self) -> anyhow::Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_build_a_plain_client() {
+        assert!(HttpOptions::default().build_client().is_ok());
+    }
+
+    #[test]
+    fn invalid_proxy_url_is_rejected() {
+        let opts = HttpOptions {
+            proxy: Some("not a url".into()),
+            ..Default::default()
+        };
+        assert!(opts.build_client().is_err());
+    }
+}
@@ -1,6 +1,12 @@
 pub mod anthropic;
+pub mod config;
+pub mod failover;
+pub mod http_options;
 pub mod openai;
 
+pub use failover::FallbackProvider;
+pub use http_options::HttpOptions;
+
 #[cfg(feature = "provider-genai")]
 pub mod genai_provider;
 
@@ -10,7 +16,63 @@ pub mod async_openai_provider;
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::model::LlmProvider;
+use crate::model::{CompletionResponse, LlmProvider, ToolCall};
+
+/// Round-trips a provider will take dispatching tool calls before [`run_tool_loop`]
+/// gives up, to bound runaway loops where the model keeps calling tools forever.
+const MAX_TOOL_STEPS: usize = 8;
+
+/// Resolves and executes a single tool call, returning its result as the string fed
+/// back to the model in a `{"role":"tool", ...}` message.
+#[async_trait::async_trait]
+pub trait ToolDispatcher: Send + Sync {
+    async fn dispatch(&self, call: &ToolCall) -> anyhow::Result<String>;
+}
+
+/// Drive `provider` through OpenAI-style multi-step tool calling: while the model
+/// returns tool calls, dispatch each via `dispatcher`, append an assistant message
+/// carrying the `tool_calls` plus one `tool` message per result, and re-invoke
+/// `complete`. Stops once the model returns plain text, or after [`MAX_TOOL_STEPS`]
+/// round-trips, whichever comes first.
+pub async fn run_tool_loop(
+    provider: &dyn LlmProvider,
+    messages: &mut Vec<serde_json::Value>,
+    tools: &[serde_json::Value],
+    dispatcher: &dyn ToolDispatcher,
+) -> anyhow::Result<CompletionResponse> {
+    for _ in 0..MAX_TOOL_STEPS {
+        let response = provider.complete(messages, tools).await?;
+        if response.tool_calls.is_empty() {
+            return Ok(response);
+        }
+
+        messages.push(serde_json::json!({
+            "role": "assistant",
+            "tool_calls": response.tool_calls.iter().map(|call| serde_json::json!({
+                "id": call.id,
+                "type": "function",
+                "function": { "name": call.name, "arguments": call.arguments },
+            })).collect::<Vec<_>>(),
+        }));
+
+        for call in &response.tool_calls {
+            let content = match dispatcher.dispatch(call).await {
+                Ok(result) => result,
+                Err(e) => format!("error: {e}"),
+            };
+            messages.push(serde_json::json!({
+                "role": "tool",
+                "tool_call_id": call.id,
+                "content": content,
+            }));
+        }
+    }
+
+    anyhow::bail!("exceeded {MAX_TOOL_STEPS} tool-call steps without a final response")
+}
+
+/// Default `max_tokens` for providers whose config doesn't declare one explicitly.
+pub(crate) const DEFAULT_MAX_TOKENS: u32 = 4096;
 
 /// Info about an available model.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -18,6 +80,7 @@ pub struct ModelInfo {
     pub id: String,
     pub provider: String,
     pub display_name: String,
+    pub max_tokens: u32,
 }
 
 /// Registry of available LLM providers, keyed by model ID.
@@ -119,6 +182,7 @@ impl ProviderRegistry {
                         id: model_id.into(),
                         provider: provider_name.into(),
                         display_name: display_name.into(),
+                        max_tokens: DEFAULT_MAX_TOKENS,
                     },
                     provider,
                 );
@@ -145,6 +209,7 @@ impl ProviderRegistry {
                         id: model_id.into(),
                         provider: "async-openai".into(),
                         display_name: "GPT-4o (async-openai)".into(),
+                        max_tokens: DEFAULT_MAX_TOKENS,
                     },
                     provider,
                 );
@@ -163,12 +228,14 @@ impl ProviderRegistry {
                     key,
                     model_id.into(),
                     base_url,
+                    DEFAULT_MAX_TOKENS,
                 ));
                 self.register(
                     ModelInfo {
                         id: model_id.into(),
                         provider: "anthropic".into(),
                         display_name: "Claude Sonnet 4".into(),
+                        max_tokens: DEFAULT_MAX_TOKENS,
                     },
                     provider,
                 );
@@ -190,6 +257,7 @@ impl ProviderRegistry {
                         id: model_id.into(),
                         provider: "openai".into(),
                         display_name: "GPT-4o".into(),
+                        max_tokens: DEFAULT_MAX_TOKENS,
                     },
                     provider,
                 );
@@ -208,6 +276,18 @@ impl ProviderRegistry {
             .cloned()
     }
 
+    /// Build a [`FallbackProvider`] chaining the providers behind `model_ids`, in
+    /// order, skipping any id that isn't registered. Returns `None` if none of them
+    /// resolve to a provider.
+    pub fn with_fallback(&self, model_ids: &[&str]) -> Option<Arc<dyn LlmProvider>> {
+        let chain: Vec<Arc<dyn LlmProvider>> = model_ids.iter().filter_map(|id| self.get(id)).collect();
+        if chain.is_empty() {
+            None
+        } else {
+            Some(Arc::new(FallbackProvider::new(chain)))
+        }
+    }
+
     pub fn list_models(&self) -> &[ModelInfo] {
         &self.models
     }
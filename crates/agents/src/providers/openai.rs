@@ -4,7 +4,8 @@ use async_trait::async_trait;
 use futures::StreamExt;
 use tokio_stream::Stream;
 
-use crate::model::{CompletionResponse, LlmProvider, StreamEvent, Usage};
+use crate::model::{CompletionResponse, LlmProvider, StreamEvent, ToolCall, Usage};
+use crate::providers::HttpOptions;
 
 pub struct OpenAiProvider {
     api_key: String,
@@ -15,15 +16,46 @@ pub struct OpenAiProvider {
 
 impl OpenAiProvider {
     pub fn new(api_key: String, model: String, base_url: String) -> Self {
+        Self::with_http_options(api_key, model, base_url, HttpOptions::default())
+    }
+
+    pub fn with_http_options(api_key: String, model: String, base_url: String, http: HttpOptions) -> Self {
         Self {
             api_key,
             model,
             base_url,
-            client: reqwest::Client::new(),
+            client: http.build_client().unwrap_or_else(|_| reqwest::Client::new()),
         }
     }
 }
 
+/// One tool call being assembled from streamed `delta.tool_calls` fragments.
+#[derive(Default)]
+struct StreamingToolCall {
+    id: Option<String>,
+    name: Option<String>,
+    arguments: String,
+}
+
+impl StreamingToolCall {
+    fn finish(self) -> Option<ToolCall> {
+        Some(ToolCall {
+            id: self.id?,
+            name: self.name?,
+            arguments: self.arguments,
+        })
+    }
+}
+
+/// Parse one entry of a non-streaming response's `message.tool_calls` array.
+fn parse_tool_call(raw: &serde_json::Value) -> Option<ToolCall> {
+    Some(ToolCall {
+        id: raw["id"].as_str()?.to_string(),
+        name: raw["function"]["name"].as_str()?.to_string(),
+        arguments: raw["function"]["arguments"].as_str().unwrap_or("{}").to_string(),
+    })
+}
+
 #[async_trait]
 impl LlmProvider for OpenAiProvider {
     fn name(&self) -> &str {
@@ -37,13 +69,22 @@ impl LlmProvider for OpenAiProvider {
     async fn complete(
         &self,
         messages: &[serde_json::Value],
-        _tools: &[serde_json::Value],
+        tools: &[serde_json::Value],
     ) -> anyhow::Result<CompletionResponse> {
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "model": self.model,
             "messages": messages,
         });
 
+        if !tools.is_empty() {
+            body["tools"] = serde_json::Value::Array(
+                tools
+                    .iter()
+                    .map(|tool| serde_json::json!({ "type": "function", "function": tool }))
+                    .collect(),
+            );
+        }
+
         let resp = self
             .client
             .post(format!("{}/chat/completions", self.base_url))
@@ -60,6 +101,11 @@ impl LlmProvider for OpenAiProvider {
             .as_str()
             .map(|s| s.to_string());
 
+        let tool_calls = resp["choices"][0]["message"]["tool_calls"]
+            .as_array()
+            .map(|calls| calls.iter().filter_map(parse_tool_call).collect())
+            .unwrap_or_default();
+
         let usage = Usage {
             input_tokens: resp["usage"]["prompt_tokens"].as_u64().unwrap_or(0) as u32,
             output_tokens: resp["usage"]["completion_tokens"].as_u64().unwrap_or(0) as u32,
@@ -67,7 +113,7 @@ impl LlmProvider for OpenAiProvider {
 
         Ok(CompletionResponse {
             text,
-            tool_calls: vec![],
+            tool_calls,
             usage,
         })
     }
@@ -76,15 +122,26 @@ impl LlmProvider for OpenAiProvider {
     fn stream(
         &self,
         messages: Vec<serde_json::Value>,
+        tools: &[serde_json::Value],
     ) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send + '_>> {
+        let tools = tools.to_vec();
         Box::pin(async_stream::stream! {
-            let body = serde_json::json!({
+            let mut body = serde_json::json!({
                 "model": self.model,
                 "messages": messages,
                 "stream": true,
                 "stream_options": { "include_usage": true },
             });
 
+            if !tools.is_empty() {
+                body["tools"] = serde_json::Value::Array(
+                    tools
+                        .iter()
+                        .map(|tool| serde_json::json!({ "type": "function", "function": tool }))
+                        .collect(),
+                );
+            }
+
             let resp = match self
                 .client
                 .post(format!("{}/chat/completions", self.base_url))
@@ -113,6 +170,10 @@ impl LlmProvider for OpenAiProvider {
             let mut buf = String::new();
             let mut input_tokens: u32 = 0;
             let mut output_tokens: u32 = 0;
+            // Streamed tool calls arrive fragmented across many `delta` events, keyed by
+            // their position in the `tool_calls` array; `arguments` is a partial JSON
+            // string that must be concatenated in order before it can be parsed.
+            let mut tool_calls: std::collections::BTreeMap<u64, StreamingToolCall> = std::collections::BTreeMap::new();
 
             while let Some(chunk) = byte_stream.next().await {
                 let chunk = match chunk {
@@ -153,6 +214,32 @@ impl LlmProvider for OpenAiProvider {
                                 yield StreamEvent::Delta(delta.to_string());
                             }
                         }
+
+                        if let Some(deltas) = evt["choices"][0]["delta"]["tool_calls"].as_array() {
+                            for delta in deltas {
+                                let index = delta["index"].as_u64().unwrap_or(0);
+                                let entry = tool_calls.entry(index).or_default();
+                                if let Some(id) = delta["id"].as_str() {
+                                    entry.id = Some(id.to_string());
+                                }
+                                if let Some(name) = delta["function"]["name"].as_str() {
+                                    entry.name = Some(name.to_string());
+                                }
+                                if let Some(args) = delta["function"]["arguments"].as_str() {
+                                    entry.arguments.push_str(args);
+                                }
+                            }
+                        }
+
+                        if evt["choices"][0]["finish_reason"].as_str() == Some("tool_calls") {
+                            let calls: Vec<ToolCall> = std::mem::take(&mut tool_calls)
+                                .into_values()
+                                .filter_map(StreamingToolCall::finish)
+                                .collect();
+                            if !calls.is_empty() {
+                                yield StreamEvent::ToolCalls(calls);
+                            }
+                        }
                     }
                 }
             }
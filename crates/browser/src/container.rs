@@ -9,6 +9,53 @@ use {
     tracing::{debug, info, warn},
 };
 
+/// What a caller needs to drive a sandboxed browser instance, regardless of how it was
+/// launched (a Docker container vs. a local WebDriver-compatible binary).
+pub trait BrowserBackend: Send + Sync {
+    /// The WebSocket URL for CDP connections.
+    fn websocket_url(&self) -> String;
+    /// The HTTP URL for health checks / the WebDriver API.
+    fn http_url(&self) -> String;
+    /// An opaque identifier for this instance (container ID or WebDriver session ID).
+    fn id(&self) -> &str;
+}
+
+impl BrowserBackend for BrowserContainer {
+    fn websocket_url(&self) -> String {
+        BrowserContainer::websocket_url(self)
+    }
+
+    fn http_url(&self) -> String {
+        BrowserContainer::http_url(self)
+    }
+
+    fn id(&self) -> &str {
+        BrowserContainer::id(self)
+    }
+}
+
+/// Which backend to use for sandboxed browser execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserBackendKind {
+    /// `browserless/chrome` run via `docker run`.
+    Docker,
+    /// A local `chromedriver`/`geckodriver` binary driven over the W3C WebDriver protocol.
+    WebDriver,
+}
+
+impl BrowserBackendKind {
+    /// Pick a backend automatically: prefer Docker, fall back to WebDriver when Docker
+    /// isn't available on this host (CI sandboxes, corporate laptops without Docker).
+    #[must_use]
+    pub fn detect() -> Self {
+        if is_docker_available() {
+            Self::Docker
+        } else {
+            Self::WebDriver
+        }
+    }
+}
+
 /// A running browser container instance.
 pub struct BrowserContainer {
     /// Container ID.
@@ -66,9 +113,13 @@ impl BrowserContainer {
         }
 
         debug!(container_id, host_port, "browser container started");
+        metrics::counter!("browser_container_starts_total").increment(1);
 
         // Wait for the container to be ready
+        let wait_start = std::time::Instant::now();
         wait_for_ready(host_port)?;
+        metrics::histogram!("browser_container_wait_for_ready_seconds")
+            .record(wait_start.elapsed().as_secs_f64());
 
         info!(container_id, host_port, "browser container ready");
 
@@ -103,6 +154,7 @@ impl BrowserContainer {
         match result {
             Ok(output) if output.status.success() => {
                 debug!(container_id = %self.container_id, "browser container stopped");
+                metrics::counter!("browser_container_stops_total").increment(1);
             },
             Ok(output) => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
@@ -136,7 +188,7 @@ impl Drop for BrowserContainer {
 }
 
 /// Find an available TCP port.
-fn find_available_port() -> Result<u16> {
+pub(crate) fn find_available_port() -> Result<u16> {
     // Bind to port 0 to get a random available port
     let listener =
         std::net::TcpListener::bind("127.0.0.1:0").context("failed to bind to ephemeral port")?;
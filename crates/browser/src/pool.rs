@@ -0,0 +1,170 @@
+//! Resource-aware pool of warm [`BrowserContainer`]s.
+//!
+//! `BrowserContainer::start` used in isolation starts one container per call with no
+//! cap on how many run concurrently. `BrowserPool` owns a bounded set of containers,
+//! hands them out via an RAII [`PooledBrowser`] guard, and periodically health-checks
+//! idle containers over HTTP (browserless's `/json/version`) rather than the raw TCP
+//! probe `wait_for_ready` uses at startup, replacing any container that stops
+//! responding so callers never get handed a dead CDP socket.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use sysinfo::System;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, info, warn};
+
+use crate::container::BrowserContainer;
+
+/// Each browserless container is started with `--shm-size=2gb`; don't run more
+/// containers at once than the host can back with that much shared memory.
+const SHM_SIZE_PER_CONTAINER_MB: u64 = 2048;
+
+/// How often idle containers are health-checked in the background.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A bounded, self-healing pool of warm browser containers.
+pub struct BrowserPool {
+    image: String,
+    viewport_width: u32,
+    viewport_height: u32,
+    idle: Mutex<Vec<BrowserContainer>>,
+    semaphore: Arc<Semaphore>,
+    http: reqwest::Client,
+}
+
+impl BrowserPool {
+    /// Build a pool sized to the host's available memory, capped at one container per
+    /// `SHM_SIZE_PER_CONTAINER_MB` of free RAM (minimum of one).
+    #[must_use]
+    pub fn new(image: &str, viewport_width: u32, viewport_height: u32) -> Arc<Self> {
+        let max_containers = Self::capacity_from_available_memory();
+        info!(image, max_containers, "sizing browser pool from available memory");
+
+        Arc::new(Self {
+            image: image.to_string(),
+            viewport_width,
+            viewport_height,
+            idle: Mutex::new(Vec::new()),
+            semaphore: Arc::new(Semaphore::new(max_containers)),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    fn capacity_from_available_memory() -> usize {
+        let mut sys = System::new();
+        sys.refresh_memory();
+        let available_mb = sys.available_memory() / 1024 / 1024;
+        std::cmp::max(1, (available_mb / SHM_SIZE_PER_CONTAINER_MB) as usize)
+    }
+
+    /// Start a background task that periodically health-checks idle containers and
+    /// discards any that no longer respond, so `acquire` never has to find out the
+    /// hard way. Call once per pool; the task runs for the pool's lifetime.
+    pub fn spawn_health_monitor(self: &Arc<Self>) {
+        let pool = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+                pool.reap_unhealthy_idle().await;
+            }
+        });
+    }
+
+    async fn reap_unhealthy_idle(&self) {
+        let mut idle = self.idle.lock().await;
+        let candidates = std::mem::take(&mut *idle);
+        for container in candidates {
+            if self.is_healthy(&container).await {
+                idle.push(container);
+            } else {
+                warn!(container_id = container.id(), "idle browser container failed health check, replacing");
+                // Dropping it here runs `BrowserContainer::stop` via its `Drop` impl.
+            }
+        }
+    }
+
+    /// Check a container's liveness via browserless's HTTP endpoint rather than a raw
+    /// TCP probe, so a container that accepts connections but has wedged Chrome is
+    /// still detected as unhealthy.
+    async fn is_healthy(&self, container: &BrowserContainer) -> bool {
+        let url = format!("{}/json/version", container.http_url());
+        match self.http.get(&url).timeout(Duration::from_secs(3)).send().await {
+            Ok(resp) => resp.status().is_success(),
+            Err(e) => {
+                debug!(container_id = container.id(), error = %e, "browser container health check failed");
+                false
+            },
+        }
+    }
+
+    /// Acquire a container from the pool, reusing a healthy idle one if available,
+    /// otherwise starting a fresh one. Blocks until a concurrency slot frees up.
+    pub async fn acquire(self: &Arc<Self>) -> Result<PooledBrowser> {
+        let permit = Arc::clone(&self.semaphore).acquire_owned().await.expect("semaphore never closed");
+
+        loop {
+            let candidate = self.idle.lock().await.pop();
+            let Some(container) = candidate else {
+                break;
+            };
+            if self.is_healthy(&container).await {
+                return Ok(PooledBrowser {
+                    container: Some(container),
+                    pool: Arc::clone(self),
+                    _permit: Some(permit),
+                });
+            }
+            debug!(container_id = container.id(), "discarding unhealthy idle container");
+        }
+
+        let container = BrowserContainer::start(&self.image, self.viewport_width, self.viewport_height)?;
+        Ok(PooledBrowser {
+            container: Some(container),
+            pool: Arc::clone(self),
+            _permit: Some(permit),
+        })
+    }
+
+    /// Return a container to the idle set for reuse, unless it's no longer healthy.
+    async fn release(&self, container: BrowserContainer) {
+        if self.is_healthy(&container).await {
+            self.idle.lock().await.push(container);
+        }
+        // Unhealthy containers are dropped here, running `BrowserContainer::stop`.
+    }
+}
+
+/// An RAII handle to a pooled [`BrowserContainer`]. Dropping it returns the container
+/// to the pool (after a health check) and frees its concurrency slot — the slot is
+/// held until that health check/reinsertion finishes, not freed as soon as `drop` runs.
+pub struct PooledBrowser {
+    container: Option<BrowserContainer>,
+    pool: Arc<BrowserPool>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl std::ops::Deref for PooledBrowser {
+    type Target = BrowserContainer;
+
+    fn deref(&self) -> &BrowserContainer {
+        self.container.as_ref().expect("container taken only on drop")
+    }
+}
+
+impl Drop for PooledBrowser {
+    fn drop(&mut self) {
+        if let Some(container) = self.container.take() {
+            let pool = Arc::clone(&self.pool);
+            // Hold the permit until `release` (health check + reinsert-to-idle-or-stop)
+            // finishes, so the concurrency slot isn't freed while the old container may
+            // still be alive and a concurrent `acquire` could start a new one.
+            let permit = self._permit.take();
+            tokio::spawn(async move {
+                pool.release(container).await;
+                drop(permit);
+            });
+        }
+    }
+}
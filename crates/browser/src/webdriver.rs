@@ -0,0 +1,189 @@
+//! Local WebDriver backend — drives a `chromedriver`/`geckodriver` binary over the W3C
+//! WebDriver protocol instead of `docker run browserless/chrome`.
+//!
+//! Useful wherever Docker isn't available (CI sandboxes, corporate laptops) but a local
+//! WebDriver binary is on `PATH`.
+
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use tracing::{debug, info, warn};
+
+use crate::container::{find_available_port, BrowserBackend, BrowserBackendKind, BrowserContainer};
+
+/// Launch a sandboxed browser instance using `kind`, falling back to the binary named by
+/// `webdriver_binary` (e.g. `"chromedriver"`) when `kind` is [`BrowserBackendKind::WebDriver`].
+pub async fn launch(
+    kind: BrowserBackendKind,
+    image: &str,
+    webdriver_binary: &str,
+    viewport_width: u32,
+    viewport_height: u32,
+) -> Result<Box<dyn BrowserBackend>> {
+    match kind {
+        BrowserBackendKind::Docker => {
+            let container = BrowserContainer::start(image, viewport_width, viewport_height)?;
+            Ok(Box::new(container))
+        },
+        BrowserBackendKind::WebDriver => {
+            let browser =
+                WebDriverBrowser::start(webdriver_binary, viewport_width, viewport_height).await?;
+            Ok(Box::new(browser))
+        },
+    }
+}
+
+/// A browser session launched through a local WebDriver binary.
+pub struct WebDriverBrowser {
+    child: Child,
+    port: u16,
+    session_id: String,
+}
+
+impl WebDriverBrowser {
+    /// Spawn `driver_binary` on an ephemeral port and open a new WebDriver session with
+    /// the requested viewport size.
+    ///
+    /// `driver_binary` is something on `PATH` (or an absolute path) implementing the W3C
+    /// WebDriver protocol, e.g. `chromedriver` or `geckodriver`.
+    pub async fn start(driver_binary: &str, viewport_width: u32, viewport_height: u32) -> Result<Self> {
+        let port = find_available_port()?;
+
+        info!(driver_binary, port, "starting local WebDriver binary");
+
+        let child = Command::new(driver_binary)
+            .arg(format!("--port={port}"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("failed to spawn WebDriver binary '{driver_binary}'"))?;
+
+        wait_for_driver_ready(port).await?;
+
+        let session_id = create_session(port, viewport_width, viewport_height).await?;
+
+        info!(port, session_id, "WebDriver session ready");
+
+        Ok(Self {
+            child,
+            port,
+            session_id,
+        })
+    }
+
+    fn base_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    /// Stop the WebDriver session and kill the driver process.
+    pub async fn stop(&mut self) {
+        let url = format!("{}/session/{}", self.base_url(), self.session_id);
+        let client = reqwest::Client::new();
+        if let Err(e) = client.delete(&url).send().await {
+            warn!(session_id = %self.session_id, error = %e, "failed to delete WebDriver session");
+        }
+
+        self.kill_child();
+    }
+
+    fn kill_child(&mut self) {
+        if let Err(e) = self.child.kill() {
+            warn!(error = %e, "failed to kill WebDriver process");
+        }
+        let _ = self.child.wait();
+    }
+}
+
+impl BrowserBackend for WebDriverBrowser {
+    fn websocket_url(&self) -> String {
+        // Chrome/Firefox's CDP endpoint is reachable through the driver's own port once
+        // a session is open; chromedriver exposes it at `/session/<id>/se/cdp`.
+        format!(
+            "ws://127.0.0.1:{}/session/{}/se/cdp",
+            self.port, self.session_id
+        )
+    }
+
+    fn http_url(&self) -> String {
+        self.base_url()
+    }
+
+    fn id(&self) -> &str {
+        &self.session_id
+    }
+}
+
+impl Drop for WebDriverBrowser {
+    fn drop(&mut self) {
+        // Ending the WebDriver session is an HTTP DELETE; `reqwest::blocking` can't be
+        // used here since `Drop` may run inside a Tokio runtime (it panics with "Cannot
+        // drop a runtime in a context where blocking is not allowed"). Kill the driver
+        // process synchronously right away, then defer the session-delete HTTP call to
+        // a detached task, mirroring `PooledBrowser`'s `Drop` in `pool.rs`.
+        self.kill_child();
+
+        let url = format!("{}/session/{}", self.base_url(), self.session_id);
+        let session_id = self.session_id.clone();
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            if let Err(e) = client.delete(&url).send().await {
+                warn!(session_id = %session_id, error = %e, "failed to delete WebDriver session");
+            }
+        });
+    }
+}
+
+/// Poll the driver's `/status` endpoint until it responds or times out.
+async fn wait_for_driver_ready(port: u16) -> Result<()> {
+    let url = format!("http://127.0.0.1:{port}/status");
+    let client = reqwest::Client::new();
+    let timeout = Duration::from_secs(15);
+    let start = Instant::now();
+
+    loop {
+        if start.elapsed() > timeout {
+            bail!("WebDriver binary failed to become ready within {}s", timeout.as_secs());
+        }
+
+        if client.get(&url).send().await.is_ok() {
+            debug!("WebDriver binary is ready");
+            return Ok(());
+        }
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// POST a new `/session` with the desired capabilities, returning the session ID.
+async fn create_session(port: u16, viewport_width: u32, viewport_height: u32) -> Result<String> {
+    let url = format!("http://127.0.0.1:{port}/session");
+    let window_size_arg = format!("--window-size={viewport_width},{viewport_height}");
+
+    let body = serde_json::json!({
+        "capabilities": {
+            "alwaysMatch": {
+                "goog:chromeOptions": { "args": [window_size_arg.clone(), "--headless"] },
+                "moz:firefoxOptions": { "args": [window_size_arg] },
+            }
+        }
+    });
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .context("failed to POST /session to WebDriver binary")?
+        .error_for_status()
+        .context("WebDriver rejected the new session request")?
+        .json::<serde_json::Value>()
+        .await
+        .context("failed to parse WebDriver /session response")?;
+
+    resp["value"]["sessionId"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("WebDriver /session response missing sessionId"))
+}
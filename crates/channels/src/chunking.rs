@@ -0,0 +1,111 @@
+//! UTF-8-safe splitting of outbound text over a platform's per-message length limit
+//! (Telegram's 4096 chars, Discord's 2000, ...), so a verbose reply is sent as several
+//! messages instead of being dropped or truncated by the platform.
+
+/// Split `text` into chunks no longer than `max` chars, never breaking a UTF-8 code
+/// point, and preferring to break at the last newline or space before the limit so
+/// words and lines stay intact where possible.
+#[must_use]
+pub fn chunk_message(text: &str, max: usize) -> MessageChunks<'_> {
+    MessageChunks { remaining: text, max: max.max(1) }
+}
+
+/// Iterator over `&str` slices, each at most `max` chars, produced by [`chunk_message`].
+pub struct MessageChunks<'a> {
+    remaining: &'a str,
+    max: usize,
+}
+
+impl<'a> Iterator for MessageChunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        if self.remaining.len() <= self.max {
+            let chunk = self.remaining;
+            self.remaining = "";
+            return Some(chunk);
+        }
+
+        // Find the largest valid char boundary at or below `max` bytes.
+        let mut boundary = self.max;
+        while boundary > 0 && !self.remaining.is_char_boundary(boundary) {
+            boundary -= 1;
+        }
+
+        // `max` is smaller than the leading char's UTF-8 width, so no boundary above 0
+        // fits — yield that one char as its own (oversized) chunk rather than looping
+        // forever producing empty splits.
+        if boundary == 0 {
+            let lead_len = self.remaining.chars().next().map_or(1, char::len_utf8);
+            let (chunk, rest) = self.remaining.split_at(lead_len);
+            self.remaining = rest;
+            return Some(chunk);
+        }
+
+        // Prefer breaking at the last newline or space before that boundary, so we
+        // don't cut mid-word/mid-line when a clean break is available nearby.
+        let split_at = self.remaining[..boundary]
+            .rfind(['\n', ' '])
+            .map_or(boundary, |pos| pos + 1);
+
+        let (chunk, rest) = self.remaining.split_at(split_at);
+        self.remaining = rest;
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_a_single_chunk() {
+        let chunks: Vec<_> = chunk_message("hello", 4096).collect();
+        assert_eq!(chunks, vec!["hello"]);
+    }
+
+    #[test]
+    fn splits_on_word_boundary() {
+        let text = "aaaa bbbb cccc";
+        let chunks: Vec<_> = chunk_message(text, 9).collect();
+        assert_eq!(chunks, vec!["aaaa ", "bbbb cccc"]);
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[test]
+    fn splits_on_newline_before_space() {
+        let text = "line one\nline two";
+        let chunks: Vec<_> = chunk_message(text, 10).collect();
+        assert_eq!(chunks, vec!["line one\n", "line two"]);
+    }
+
+    #[test]
+    fn never_splits_a_utf8_code_point() {
+        let text = "a".repeat(5) + "日本語" + &"b".repeat(5);
+        for chunk in chunk_message(&text, 6) {
+            assert!(chunk.is_char_boundary(0) && chunk.is_char_boundary(chunk.len()));
+        }
+        let chunks: Vec<_> = chunk_message(&text, 6).collect();
+        assert_eq!(chunks.concat(), text);
+    }
+
+    #[test]
+    fn falls_back_to_hard_break_with_no_whitespace() {
+        let text = "a".repeat(20);
+        let chunks: Vec<_> = chunk_message(&text, 8).collect();
+        assert_eq!(chunks, vec!["a".repeat(8), "a".repeat(8), "a".repeat(4)]);
+    }
+
+    #[test]
+    fn terminates_when_max_is_smaller_than_a_leading_multibyte_char() {
+        // Each of these chars is 3 bytes; `max: 2` can't fit even one.
+        let text = "日本語abc";
+        let chunks: Vec<_> = chunk_message(text, 2).collect();
+        assert_eq!(chunks.concat(), text);
+        assert_eq!(chunks, vec!["日", "本", "語", "ab", "c"]);
+    }
+}
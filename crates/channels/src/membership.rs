@@ -0,0 +1,14 @@
+//! Shared shape for a sender's standing within a group/guild, so `access::check_access`
+//! in each channel plugin can gate on admin status or role membership without
+//! depending on that platform's own SDK types.
+
+/// A sender's resolved standing within the group/guild a message arrived in.
+#[derive(Debug, Clone, Default)]
+pub struct MemberInfo {
+    /// The sender is a chat admin/owner (Telegram `getChatMember` status, Discord
+    /// `ADMINISTRATOR`/owner).
+    pub is_admin: bool,
+    /// Role names held by the sender (Discord guild roles; empty for Telegram, which
+    /// has no role concept beyond admin).
+    pub roles: Vec<String>,
+}
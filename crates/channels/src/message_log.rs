@@ -0,0 +1,266 @@
+//! Persistent log of inbound channel messages. Every message is recorded here
+//! regardless of whether access was granted, both for audit and — via [`MessageLog::history`]
+//! — to ground the reply pipeline's multi-turn memory in already-persisted data instead
+//! of a separate conversation store.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+
+/// One inbound message, recorded whether or not access was granted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageLogEntry {
+    pub id: i64,
+    pub account_id: String,
+    pub channel_type: String,
+    pub peer_id: String,
+    pub username: Option<String>,
+    pub sender_name: Option<String>,
+    pub chat_id: String,
+    pub chat_type: String,
+    pub body: String,
+    pub access_granted: bool,
+    pub created_at: i64,
+}
+
+/// Per-account knobs controlling how much history [`MessageLog::history`] hands back,
+/// parsed from the account's own JSON config (the same blob `ChannelPlugin::start_account`
+/// receives) so it can be tuned per account rather than globally.
+#[derive(Debug, Clone, Copy)]
+pub struct HistoryConfig {
+    /// Maximum number of prior turns to return.
+    pub depth: usize,
+    /// Only consider messages within the last `window_secs` seconds, if set.
+    pub window_secs: Option<i64>,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { depth: 20, window_secs: None }
+    }
+}
+
+impl HistoryConfig {
+    /// Parse `{"history": {"depth": N, "window_secs": N}}` out of an account config
+    /// blob, falling back to defaults for any field that's missing or malformed.
+    #[must_use]
+    pub fn from_account_config(config: &serde_json::Value) -> Self {
+        let history = &config["history"];
+        let defaults = Self::default();
+        Self {
+            depth: history["depth"].as_u64().map_or(defaults.depth, |d| d as usize),
+            window_secs: history["window_secs"].as_i64().or(defaults.window_secs),
+        }
+    }
+}
+
+/// Where inbound messages are recorded, and read back as conversation history.
+#[async_trait]
+pub trait MessageLog: Send + Sync {
+    /// Record one inbound message.
+    async fn log(&self, entry: MessageLogEntry) -> anyhow::Result<()>;
+
+    /// The last `config.depth` granted entries logged for `account_id`/`chat_id`,
+    /// oldest-to-newest, optionally restricted to `config.window_secs`. Denied
+    /// messages are excluded since the model never saw or answered them.
+    async fn history(
+        &self,
+        account_id: &str,
+        chat_id: &str,
+        config: HistoryConfig,
+    ) -> anyhow::Result<Vec<MessageLogEntry>>;
+}
+
+/// JSONL-backed [`MessageLog`]: one append-only file per account under
+/// `~/.moltis/messages/`, with an in-memory index for fast history slicing.
+pub struct FileMessageLog {
+    dir: PathBuf,
+    loaded_accounts: Mutex<HashSet<String>>,
+    entries: Mutex<HashMap<(String, String), Vec<MessageLogEntry>>>,
+}
+
+impl FileMessageLog {
+    #[must_use]
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            loaded_accounts: Mutex::new(HashSet::new()),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Open the default log directory, `~/.moltis/messages/`.
+    pub fn open_default() -> anyhow::Result<Self> {
+        let home = directories::BaseDirs::new()
+            .ok_or_else(|| anyhow::anyhow!("could not determine home directory"))?;
+        Ok(Self::new(home.home_dir().join(".moltis/messages")))
+    }
+
+    fn log_path(&self, account_id: &str) -> PathBuf {
+        self.dir.join(format!("{account_id}.jsonl"))
+    }
+
+    /// Load `account_id`'s log file into the in-memory index, if it hasn't been
+    /// already. A no-op after the first call, or if the file doesn't exist yet.
+    async fn ensure_loaded(&self, account_id: &str) -> anyhow::Result<()> {
+        if self.loaded_accounts.lock().await.contains(account_id) {
+            return Ok(());
+        }
+
+        let path = self.log_path(account_id);
+        let mut by_chat: HashMap<(String, String), Vec<MessageLogEntry>> = HashMap::new();
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                for line in contents.lines().filter(|l| !l.is_empty()) {
+                    match serde_json::from_str::<MessageLogEntry>(line) {
+                        Ok(entry) => by_chat
+                            .entry((entry.account_id.clone(), entry.chat_id.clone()))
+                            .or_default()
+                            .push(entry),
+                        Err(e) => tracing::warn!(account_id, error = %e, "skipping malformed message log line"),
+                    }
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut entries = self.entries.lock().await;
+        for (key, mut loaded) in by_chat {
+            entries.entry(key).or_default().append(&mut loaded);
+        }
+        self.loaded_accounts.lock().await.insert(account_id.to_string());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageLog for FileMessageLog {
+    async fn log(&self, entry: MessageLogEntry) -> anyhow::Result<()> {
+        self.ensure_loaded(&entry.account_id).await?;
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.log_path(&entry.account_id);
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(line.as_bytes()).await?;
+
+        self.entries
+            .lock()
+            .await
+            .entry((entry.account_id.clone(), entry.chat_id.clone()))
+            .or_default()
+            .push(entry);
+        Ok(())
+    }
+
+    async fn history(
+        &self,
+        account_id: &str,
+        chat_id: &str,
+        config: HistoryConfig,
+    ) -> anyhow::Result<Vec<MessageLogEntry>> {
+        self.ensure_loaded(account_id).await?;
+
+        let entries = self.entries.lock().await;
+        let Some(all) = entries.get(&(account_id.to_string(), chat_id.to_string())) else {
+            return Ok(Vec::new());
+        };
+
+        let cutoff = config.window_secs.map(|w| unix_now() - w);
+        let filtered: Vec<MessageLogEntry> = all
+            .iter()
+            .filter(|e| e.access_granted)
+            .filter(|e| cutoff.map_or(true, |c| e.created_at >= c))
+            .cloned()
+            .collect();
+
+        let start = filtered.len().saturating_sub(config.depth);
+        Ok(filtered[start..].to_vec())
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64, chat_id: &str, body: &str, access_granted: bool, created_at: i64) -> MessageLogEntry {
+        MessageLogEntry {
+            id,
+            account_id: "acct1".into(),
+            channel_type: "telegram".into(),
+            peer_id: "user1".into(),
+            username: None,
+            sender_name: Some("Alice".into()),
+            chat_id: chat_id.into(),
+            chat_type: "dm".into(),
+            body: body.into(),
+            access_granted,
+            created_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn history_returns_oldest_to_newest_within_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = FileMessageLog::new(dir.path().to_path_buf());
+
+        for i in 0..5 {
+            log.log(entry(i, "chat1", &format!("msg{i}"), true, i)).await.unwrap();
+        }
+
+        let history = log
+            .history("acct1", "chat1", HistoryConfig { depth: 3, window_secs: None })
+            .await
+            .unwrap();
+
+        let bodies: Vec<_> = history.iter().map(|e| e.body.as_str()).collect();
+        assert_eq!(bodies, vec!["msg2", "msg3", "msg4"]);
+    }
+
+    #[tokio::test]
+    async fn denied_messages_are_excluded() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = FileMessageLog::new(dir.path().to_path_buf());
+
+        log.log(entry(0, "chat1", "granted", true, 0)).await.unwrap();
+        log.log(entry(1, "chat1", "denied", false, 1)).await.unwrap();
+
+        let history = log
+            .history("acct1", "chat1", HistoryConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].body, "granted");
+    }
+
+    #[tokio::test]
+    async fn reloads_from_disk_across_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let log = FileMessageLog::new(dir.path().to_path_buf());
+            log.log(entry(0, "chat1", "persisted", true, 0)).await.unwrap();
+        }
+
+        let log = FileMessageLog::new(dir.path().to_path_buf());
+        let history = log
+            .history("acct1", "chat1", HistoryConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].body, "persisted");
+    }
+}
@@ -1,5 +1,6 @@
 use {
-    anyhow::Result, async_trait::async_trait, moltis_common::types::ReplyPayload, tokio::sync::mpsc,
+    anyhow::Result, async_trait::async_trait, moltis_common::types::ReplyPayload,
+    serde::{Deserialize, Serialize}, tokio::sync::mpsc,
 };
 
 /// Core channel plugin trait. Each messaging platform implements this.
@@ -22,6 +23,13 @@ pub trait ChannelPlugin: Send + Sync {
 
     /// Get status adapter for health checks.
     fn status(&self) -> Option<&dyn ChannelStatus>;
+
+    /// Get inbound adapter for subscribing to incoming messages, if this backend
+    /// supports it. Backends that are outbound-only (or don't support live
+    /// subscription yet) return `None`.
+    fn inbound(&self) -> Option<&dyn ChannelInbound> {
+        None
+    }
 }
 
 /// Send messages to a channel.
@@ -37,6 +45,26 @@ pub trait ChannelStatus: Send + Sync {
     async fn probe(&self, account_id: &str) -> Result<ChannelHealthSnapshot>;
 }
 
+/// Subscribe to inbound messages arriving on a channel backend.
+#[async_trait]
+pub trait ChannelInbound: Send + Sync {
+    /// Subscribe to this backend's inbound messages, across all of its accounts.
+    /// Each call opens an independent stream; the backend fans out to all of them.
+    async fn subscribe(&self) -> mpsc::Receiver<InboundMessage>;
+}
+
+/// A message received on some channel account, ready to be fanned out to WebSocket
+/// subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundMessage {
+    pub channel_type: String,
+    pub account_id: String,
+    /// Platform-specific sender/chat identifier (e.g. a Telegram chat ID).
+    pub from: String,
+    pub text: String,
+    pub timestamp_ms: i64,
+}
+
 /// Channel health snapshot.
 #[derive(Debug, Clone)]
 pub struct ChannelHealthSnapshot {
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use clap::Subcommand;
-use moltis_oauth::{CallbackServer, OAuthConfig, OAuthFlow, TokenStore};
+use moltis_oauth::{CallbackServer, OAuthFlow, ProviderRegistry, TokenStore};
 
 #[derive(Subcommand)]
 pub enum AuthAction {
@@ -28,21 +28,25 @@ pub async fn handle_auth(action: AuthAction) -> Result<()> {
     }
 }
 
-fn oauth_config_for(provider: &str) -> Result<OAuthConfig> {
-    match provider {
-        "openai-codex" => Ok(OAuthConfig {
-            client_id: "pdlLIX2Y72MIl2rhLhTE9VV9bN905kBh".to_string(),
-            auth_url: "https://auth.openai.com/oauth/authorize".to_string(),
-            token_url: "https://auth.openai.com/oauth/token".to_string(),
-            redirect_uri: "http://127.0.0.1:1455/auth/callback".to_string(),
-            scopes: vec![],
-        }),
-        _ => anyhow::bail!("unknown provider: {provider}"),
+/// Open the token store, preferring encryption at rest and falling back to the
+/// plaintext store (with a warning) when no passphrase is configured, so `moltis auth`
+/// keeps working for users who haven't set up a keyring entry or env var yet.
+pub(crate) fn open_store() -> TokenStore {
+    match TokenStore::encrypted() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!(
+                "warning: storing tokens in plaintext ({e}); set MOLTIS_TOKEN_PASSPHRASE or \
+                 save a passphrase in the OS keyring to encrypt tokens at rest"
+            );
+            TokenStore::new()
+        },
     }
 }
 
 async fn login(provider: &str) -> Result<()> {
-    let config = oauth_config_for(provider)?;
+    let registry = ProviderRegistry::load()?;
+    let config = registry.get(provider)?;
     let flow = OAuthFlow::new(config);
     let req = flow.start();
 
@@ -57,7 +61,7 @@ async fn login(provider: &str) -> Result<()> {
     println!("Exchanging code for tokens...");
     let tokens = flow.exchange(&code, &req.pkce.verifier).await?;
 
-    let store = TokenStore::new();
+    let store = open_store();
     store.save(provider, &tokens)?;
 
     println!("Successfully logged in to {provider}");
@@ -65,36 +69,40 @@ async fn login(provider: &str) -> Result<()> {
 }
 
 fn status() -> Result<()> {
-    let store = TokenStore::new();
+    let store = open_store();
     let providers = store.list();
     if providers.is_empty() {
         println!("No authenticated providers.");
         return Ok(());
     }
     for provider in providers {
-        if let Some(tokens) = store.load(&provider) {
-            let expiry = tokens.expires_at.map_or("unknown".to_string(), |ts| {
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-                if ts > now {
-                    let remaining = ts - now;
-                    let hours = remaining / 3600;
-                    let mins = (remaining % 3600) / 60;
-                    format!("valid ({hours}h {mins}m remaining)")
-                } else {
-                    "expired".to_string()
-                }
-            });
-            println!("{provider} [{expiry}]");
+        match store.load(&provider) {
+            Ok(Some(tokens)) => {
+                let expiry = tokens.expires_at.map_or("unknown".to_string(), |ts| {
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs();
+                    if ts > now {
+                        let remaining = ts - now;
+                        let hours = remaining / 3600;
+                        let mins = (remaining % 3600) / 60;
+                        format!("valid ({hours}h {mins}m remaining)")
+                    } else {
+                        "expired".to_string()
+                    }
+                });
+                println!("{provider} [{expiry}]");
+            },
+            Ok(None) => {},
+            Err(e) => println!("{provider} [error: {e}]"),
         }
     }
     Ok(())
 }
 
 fn logout(provider: &str) -> Result<()> {
-    let store = TokenStore::new();
+    let store = open_store();
     store.delete(provider)?;
     println!("Logged out from {provider}");
     Ok(())
@@ -19,6 +19,16 @@ struct Cli {
     /// Output logs as JSON instead of human-readable.
     #[arg(long, global = true, default_value_t = false)]
     json_logs: bool,
+
+    /// OTLP/gRPC collector endpoint to export traces to (e.g. http://localhost:4317).
+    /// Falls back to `OTEL_EXPORTER_OTLP_ENDPOINT` when unset.
+    #[arg(long, global = true, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+
+    /// Address to serve Prometheus `/metrics` scrapes on (e.g. 127.0.0.1:9090). Unset
+    /// disables metrics entirely.
+    #[arg(long, global = true)]
+    metrics_addr: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -29,6 +39,15 @@ enum Commands {
         bind: String,
         #[arg(long, default_value_t = 18789)]
         port: u16,
+        /// PEM certificate chain for TLS termination. Requires `--tls-key`.
+        #[arg(long)]
+        tls_cert: Option<String>,
+        /// PEM private key for TLS termination. Requires `--tls-cert`.
+        #[arg(long)]
+        tls_key: Option<String>,
+        /// PEM CA bundle used to require and verify client certificates (mutual TLS).
+        #[arg(long)]
+        tls_client_ca: Option<String>,
     },
     /// Invoke an agent directly.
     Agent {
@@ -112,6 +131,11 @@ enum SkillAction {
         /// Skill name to remove.
         name: String,
     },
+    /// Re-fetch an installed skill from its recorded source.
+    Update {
+        /// Skill name to update.
+        name: String,
+    },
     /// Show details about a skill.
     Info {
         /// Skill name.
@@ -123,10 +147,13 @@ fn init_telemetry(cli: &Cli) {
     let filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&cli.log_level));
 
+    let otel_layer = cli.otlp_endpoint.as_deref().and_then(build_otel_layer);
+
     if cli.json_logs {
         tracing_subscriber::registry()
             .with(filter)
             .with(fmt::layer().json().with_target(true).with_thread_ids(false))
+            .with(otel_layer)
             .init();
     } else {
         tracing_subscriber::registry()
@@ -137,10 +164,51 @@ fn init_telemetry(cli: &Cli) {
                     .with_thread_ids(false)
                     .with_ansi(true),
             )
+            .with(otel_layer)
             .init();
     }
 }
 
+/// Build an OpenTelemetry tracing layer that ships spans to `endpoint` over OTLP/gRPC.
+///
+/// Returns `None` (falling back to the fmt-only layers) if the exporter fails to build,
+/// logging the reason rather than aborting startup.
+fn build_otel_layer<S>(endpoint: &str) -> Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{trace as sdktrace, Resource};
+
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("failed to build OTLP exporter for {endpoint}: {e}");
+            return None;
+        },
+    };
+
+    let resource = Resource::new(vec![
+        KeyValue::new("service.name", env!("CARGO_PKG_NAME")),
+        KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+    ]);
+
+    let provider = sdktrace::TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(resource)
+        .build();
+
+    let tracer = opentelemetry::trace::TracerProvider::tracer(&provider, env!("CARGO_PKG_NAME"));
+    let _ = opentelemetry::global::set_tracer_provider(provider);
+
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
@@ -149,9 +217,35 @@ async fn main() -> anyhow::Result<()> {
 
     info!(version = env!("CARGO_PKG_VERSION"), "moltis starting");
 
+    if let Some(addr) = &cli.metrics_addr {
+        if let Err(e) = moltis_gateway::metrics::install_prometheus_recorder(addr) {
+            eprintln!("failed to start metrics exporter: {e}");
+        }
+    }
+
     match cli.command {
-        Commands::Gateway { bind, port } => {
-            moltis_gateway::server::start_gateway(&bind, port).await
+        Commands::Gateway {
+            bind,
+            port,
+            tls_cert,
+            tls_key,
+            tls_client_ca,
+        } => {
+            let tls = moltis_gateway::tls::TlsOptions {
+                cert_path: tls_cert,
+                key_path: tls_key,
+                client_ca_path: tls_client_ca,
+            };
+
+            match moltis_oauth::ProviderRegistry::load() {
+                Ok(registry) => {
+                    let store = std::sync::Arc::new(auth_commands::open_store());
+                    moltis_oauth::refresh::spawn_refresh_task(store, std::sync::Arc::new(registry));
+                },
+                Err(e) => eprintln!("failed to load OAuth provider registry, token refresh disabled: {e}"),
+            }
+
+            moltis_gateway::server::start_gateway(&bind, port, tls).await
         },
         Commands::Agent { message, .. } => {
             let result = moltis_agents::runner::run_agent("default", "main", &message).await?;
@@ -161,6 +255,7 @@ async fn main() -> anyhow::Result<()> {
         Commands::Onboard => moltis_onboarding::wizard::run_onboarding().await,
         Commands::Auth { action } => auth_commands::handle_auth(action).await,
         Commands::Skills { action } => handle_skills(action).await,
+        Commands::Sessions { action } => handle_sessions(action).await,
         _ => {
             eprintln!("command not yet implemented");
             Ok(())
@@ -168,6 +263,38 @@ async fn main() -> anyhow::Result<()> {
     }
 }
 
+/// Talk to a locally running gateway's chat history endpoint.
+async fn handle_sessions(action: SessionAction) -> anyhow::Result<()> {
+    match action {
+        SessionAction::History { key } => {
+            let client = reqwest::Client::new();
+            let entries = client
+                .get("http://127.0.0.1:18789/api/chat/history")
+                .query(&[("key", key.as_str()), ("mode", "latest")])
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<serde_json::Value>>()
+                .await?;
+
+            if entries.is_empty() {
+                println!("No history for session '{key}'.");
+            } else {
+                for entry in &entries {
+                    let role = entry["role"].as_str().unwrap_or("?");
+                    let text = entry["text"].as_str().unwrap_or("");
+                    println!("[{role}] {text}");
+                }
+            }
+            Ok(())
+        },
+        SessionAction::List | SessionAction::Clear { .. } => {
+            eprintln!("command not yet implemented");
+            Ok(())
+        },
+    }
+}
+
 async fn handle_skills(action: SkillAction) -> anyhow::Result<()> {
     use moltis_skills::{
         discover::FsSkillDiscoverer,
@@ -206,6 +333,11 @@ async fn handle_skills(action: SkillAction) -> anyhow::Result<()> {
             registry.remove_skill(&name).await?;
             println!("Removed skill '{name}'.");
         },
+        SkillAction::Update { name } => {
+            let install_dir = install::default_install_dir()?;
+            let meta = install::update_skill(&name, &install_dir).await?;
+            println!("Updated skill '{}': {}", meta.name, meta.description);
+        },
         SkillAction::Info { name } => {
             let registry = InMemoryRegistry::from_discoverer(&discoverer).await?;
             let content = registry.load_skill(&name).await?;
@@ -216,7 +348,8 @@ async fn handle_skills(action: SkillAction) -> anyhow::Result<()> {
                 println!("License:     {license}");
             }
             if !meta.allowed_tools.is_empty() {
-                println!("Tools:       {}", meta.allowed_tools.join(", "));
+                let names: Vec<&str> = meta.allowed_tools.iter().map(|t| t.name()).collect();
+                println!("Tools:       {}", names.join(", "));
             }
             println!("Path:        {}", meta.path.display());
             println!("Source:      {:?}", meta.source);
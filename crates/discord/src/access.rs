@@ -0,0 +1,124 @@
+use moltis_channels::membership::MemberInfo;
+use moltis_common::types::ChatType;
+
+/// Per-account access policy, parsed from the account's channel config. Mirrors the
+/// shape of Telegram's access control (allow-list plus mention-gating) so the same
+/// reasoning carries over to a second platform.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct AccessConfig {
+    /// User IDs or usernames allowed to talk to the bot. Empty means "everyone".
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Guild IDs the bot will respond in. Empty means "any guild".
+    #[serde(default)]
+    pub allowed_guilds: Vec<String>,
+    /// Require an @mention before replying in a guild channel.
+    #[serde(default = "default_require_mention")]
+    pub require_mention_in_groups: bool,
+    /// Restrict guild replies to members with the `ADMINISTRATOR` permission or the
+    /// guild owner.
+    #[serde(default)]
+    pub require_admin: bool,
+    /// Restrict guild replies to members holding one of these role names.
+    #[serde(default)]
+    pub allowed_roles: Vec<String>,
+}
+
+fn default_require_mention() -> bool {
+    true
+}
+
+/// Decide whether an inbound message should be answered. DMs only check the user
+/// allow-list; guild channels additionally check the guild allow-list, require the
+/// bot to have been @mentioned unless disabled, and — if configured — require the
+/// sender to be a guild admin/owner or hold an allowed role. `member` is `None` when
+/// membership couldn't be resolved (e.g. API error); admin/role gates then deny.
+pub fn check_access(
+    config: &serde_json::Value,
+    chat_type: &ChatType,
+    peer_id: &str,
+    username: Option<&str>,
+    group_id: Option<&str>,
+    bot_mentioned: bool,
+    member: Option<&MemberInfo>,
+) -> Result<(), String> {
+    let config: AccessConfig = serde_json::from_value(config.clone()).unwrap_or_default();
+
+    if !config.allowed_users.is_empty()
+        && !config
+            .allowed_users
+            .iter()
+            .any(|u| u == peer_id || Some(u.as_str()) == username)
+    {
+        return Err(format!("user '{peer_id}' is not in the allow-list"));
+    }
+
+    match chat_type {
+        ChatType::Dm => Ok(()),
+        ChatType::Group | ChatType::Channel => {
+            if !config.allowed_guilds.is_empty() {
+                let allowed = group_id
+                    .map(|g| config.allowed_guilds.iter().any(|a| a == g))
+                    .unwrap_or(false);
+                if !allowed {
+                    return Err(format!(
+                        "guild '{}' is not in the allow-list",
+                        group_id.unwrap_or("unknown")
+                    ));
+                }
+            }
+
+            if config.require_mention_in_groups && !bot_mentioned {
+                return Err("bot was not @mentioned in the channel".to_string());
+            }
+
+            if config.require_admin || !config.allowed_roles.is_empty() {
+                let Some(member) = member else {
+                    return Err("could not resolve guild membership".to_string());
+                };
+                let role_ok = !config.allowed_roles.is_empty()
+                    && member.roles.iter().any(|r| config.allowed_roles.contains(r));
+                let admin_ok = config.require_admin && member.is_admin;
+                if !(admin_ok || role_ok) {
+                    return Err(format!("user '{peer_id}' lacks the required guild role/admin status"));
+                }
+            }
+
+            Ok(())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dm_allowed_by_default() {
+        let config = serde_json::json!({});
+        assert!(check_access(&config, &ChatType::Dm, "u1", None, None, false, None).is_ok());
+    }
+
+    #[test]
+    fn group_requires_mention_by_default() {
+        let config = serde_json::json!({});
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), false, None).is_err());
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, None).is_ok());
+    }
+
+    #[test]
+    fn user_allow_list_is_enforced() {
+        let config = serde_json::json!({ "allowed_users": ["u1"] });
+        assert!(check_access(&config, &ChatType::Dm, "u1", None, None, false, None).is_ok());
+        assert!(check_access(&config, &ChatType::Dm, "u2", None, None, false, None).is_err());
+    }
+
+    #[test]
+    fn role_gate_requires_matching_role() {
+        let config = serde_json::json!({ "allowed_roles": ["mods"] });
+        let mod_member = MemberInfo { is_admin: false, roles: vec!["mods".into()] };
+        let regular = MemberInfo { is_admin: false, roles: vec!["members".into()] };
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, Some(&mod_member)).is_ok());
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, Some(&regular)).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use serenity::all::{ChannelType, Context as SerenityContext, EventHandler, Message, Ready};
+use tracing::{debug, info, warn};
+
+use moltis_channels::message_log::MessageLogEntry;
+use moltis_common::types::{ChatType, MsgContext};
+
+use crate::{access, membership, outbound, state::AccountStateMap};
+
+/// Serenity event handler bridging Discord gateway events into the same
+/// access-control → message-log → auto-reply pipeline Telegram uses.
+pub struct DiscordHandler {
+    pub account_id: String,
+    pub accounts: AccountStateMap,
+}
+
+#[async_trait]
+impl EventHandler for DiscordHandler {
+    async fn ready(&self, _ctx: SerenityContext, ready: Ready) {
+        info!(account_id = %self.account_id, bot = %ready.user.name, "discord account connected");
+    }
+
+    async fn message(&self, ctx: SerenityContext, msg: Message) {
+        if msg.author.bot {
+            return;
+        }
+        if let Err(e) = handle_message(&ctx, &msg, &self.account_id, &self.accounts).await {
+            warn!(account_id = %self.account_id, error = %e, "failed to handle discord message");
+        }
+    }
+}
+
+async fn handle_message(
+    ctx: &SerenityContext,
+    msg: &Message,
+    account_id: &str,
+    accounts: &AccountStateMap,
+) -> anyhow::Result<()> {
+    let text = msg.content.clone();
+    if text.is_empty() && msg.attachments.is_empty() {
+        debug!(account_id, "ignoring empty, non-media message");
+        return Ok(());
+    }
+
+    let (config, message_log) = {
+        let accts = accounts.read().unwrap();
+        let Some(state) = accts.get(account_id) else {
+            warn!(account_id, "handler: account not found in state map");
+            return Ok(());
+        };
+        (state.config.clone(), state.message_log.clone())
+    };
+
+    let (chat_type, group_id, guild_id) = classify_chat(ctx, msg).await;
+    let peer_id = msg.author.id.get().to_string();
+    let username = Some(msg.author.name.clone());
+    let sender_name = username.clone();
+    let bot_mentioned = check_bot_mentioned(ctx, msg);
+
+    debug!(account_id, ?chat_type, peer_id, bot_mentioned, "checking access");
+
+    // Resolve the sender's guild membership (roles, admin status) before the access
+    // check, so `require_admin`/`allowed_roles` can gate on it.
+    let member = match msg.guild_id {
+        Some(guild_id) => membership::resolve_member(ctx, guild_id, msg.author.id).await,
+        None => None,
+    };
+
+    let access_result = access::check_access(
+        &config,
+        &chat_type,
+        &peer_id,
+        username.as_deref(),
+        group_id.as_deref(),
+        bot_mentioned,
+        member.as_ref(),
+    );
+    let access_granted = access_result.is_ok();
+
+    // Log every inbound message (before returning on denial).
+    if let Some(ref log) = message_log {
+        let chat_type_str = match chat_type {
+            ChatType::Dm => "dm",
+            ChatType::Group => "group",
+            ChatType::Channel => "channel",
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let entry = MessageLogEntry {
+            id: 0,
+            account_id: account_id.to_string(),
+            channel_type: "discord".into(),
+            peer_id: peer_id.clone(),
+            username: username.clone(),
+            sender_name: sender_name.clone(),
+            chat_id: msg.channel_id.get().to_string(),
+            chat_type: chat_type_str.into(),
+            body: text.clone(),
+            access_granted,
+            created_at: now,
+        };
+        if let Err(e) = log.log(entry).await {
+            warn!(account_id, "failed to log message: {e}");
+        }
+    }
+
+    if let Err(reason) = access_result {
+        warn!(account_id, %reason, peer_id, username = ?username, "handler: access denied");
+        return Ok(());
+    }
+
+    debug!(account_id, "handler: access granted");
+
+    let session_key = build_session_key(account_id, &chat_type, &peer_id, guild_id.as_deref());
+    let reply_to_id = msg.referenced_message.as_ref().map(|r| r.id.get().to_string());
+    let media_url = msg.attachments.first().map(|a| a.url.clone());
+
+    let msg_ctx = MsgContext {
+        body: text,
+        from: peer_id,
+        to: msg.channel_id.get().to_string(),
+        channel: "discord".into(),
+        account_id: account_id.to_string(),
+        chat_type,
+        session_key,
+        reply_to_id,
+        media_path: None,
+        media_url,
+        group_id,
+        guild_id,
+        team_id: None,
+        sender_name,
+    };
+
+    // Dispatch to auto-reply pipeline
+    match moltis_auto_reply::reply::get_reply(&msg_ctx).await {
+        Ok(reply) => {
+            info!(account_id, to = %msg_ctx.to, text = %reply.text, "sending reply");
+            if let Err(e) = outbound::send_text(&ctx.http, &msg_ctx.to, &reply.text).await {
+                warn!(account_id, "failed to send reply: {e}");
+            }
+        },
+        Err(e) => {
+            warn!(account_id, "auto-reply failed: {e}");
+        },
+    }
+
+    Ok(())
+}
+
+/// Classify chat kind and extract the channel (`group_id`) and guild (`guild_id`)
+/// identifiers. DMs have neither; guild announcement channels are treated as
+/// `ChatType::Channel`, everything else in a guild as `ChatType::Group`.
+async fn classify_chat(ctx: &SerenityContext, msg: &Message) -> (ChatType, Option<String>, Option<String>) {
+    match msg.guild_id {
+        Some(guild_id) => {
+            let group_id = msg.channel_id.get().to_string();
+            let chat_type = match msg.channel_id.to_channel(&ctx.http).await {
+                Ok(channel) => match channel.guild() {
+                    Some(gc) if gc.kind == ChannelType::News => ChatType::Channel,
+                    _ => ChatType::Group,
+                },
+                Err(_) => ChatType::Group,
+            };
+            (chat_type, Some(group_id), Some(guild_id.get().to_string()))
+        },
+        None => (ChatType::Dm, None, None),
+    }
+}
+
+/// Check if the bot was @mentioned in the message.
+fn check_bot_mentioned(ctx: &SerenityContext, msg: &Message) -> bool {
+    ctx.cache
+        .current_user()
+        .map(|me| msg.mentions_user_id(me.id))
+        .unwrap_or(false)
+}
+
+/// Build a session key. Group/channel sessions are keyed by guild rather than
+/// individual channel, so per-server config and history apply uniformly across a
+/// guild's channels.
+fn build_session_key(account_id: &str, chat_type: &ChatType, peer_id: &str, guild_id: Option<&str>) -> String {
+    match chat_type {
+        ChatType::Dm => format!("discord:{account_id}:dm:{peer_id}"),
+        ChatType::Group | ChatType::Channel => {
+            let gid = guild_id.unwrap_or("unknown");
+            format!("discord:{account_id}:guild:{gid}")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_key_dm() {
+        let key = build_session_key("bot1", &ChatType::Dm, "user123", None);
+        assert_eq!(key, "discord:bot1:dm:user123");
+    }
+
+    #[test]
+    fn session_key_group() {
+        let key = build_session_key("bot1", &ChatType::Group, "user123", Some("555"));
+        assert_eq!(key, "discord:bot1:guild:555");
+    }
+}
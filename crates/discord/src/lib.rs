@@ -0,0 +1,8 @@
+pub mod access;
+pub mod handlers;
+pub mod membership;
+pub mod outbound;
+pub mod plugin;
+pub mod state;
+
+pub use plugin::DiscordPlugin;
@@ -0,0 +1,55 @@
+//! Resolves a sender's guild roles and admin status, cached briefly so
+//! `access::check_access`'s admin/role gate doesn't cost an API round-trip on every
+//! message.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serenity::all::{Context as SerenityContext, GuildId, UserId};
+
+use moltis_channels::membership::MemberInfo;
+
+/// How long a resolved membership stays valid before the next lookup re-fetches it.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+fn cache() -> &'static Mutex<HashMap<(GuildId, UserId), (MemberInfo, Instant)>> {
+    static CACHE: OnceLock<Mutex<HashMap<(GuildId, UserId), (MemberInfo, Instant)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolve `user_id`'s roles and admin status in `guild_id`, using a cached result if
+/// it's younger than [`CACHE_TTL`]. Returns `None` if the member can't be fetched
+/// (e.g. the bot left the guild), leaving admin/role-gated access denied.
+pub async fn resolve_member(ctx: &SerenityContext, guild_id: GuildId, user_id: UserId) -> Option<MemberInfo> {
+    if let Some((info, fetched_at)) = cache().lock().unwrap().get(&(guild_id, user_id)) {
+        if fetched_at.elapsed() < CACHE_TTL {
+            return Some(info.clone());
+        }
+    }
+
+    let member = guild_id.member(&ctx.http, user_id).await.ok()?;
+    let is_owner = guild_id
+        .to_guild_cached(&ctx.cache)
+        .map(|g| g.owner_id == user_id)
+        .unwrap_or(false);
+    let is_admin = is_owner || member.permissions(&ctx.cache).map(|p| p.administrator()).unwrap_or(false);
+    let roles = guild_id
+        .to_guild_cached(&ctx.cache)
+        .map(|g| {
+            member
+                .roles
+                .iter()
+                .filter_map(|role_id| g.roles.get(role_id).map(|r| r.name.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let info = MemberInfo { is_admin, roles };
+    cache()
+        .lock()
+        .unwrap()
+        .insert((guild_id, user_id), (info.clone(), Instant::now()));
+
+    Some(info)
+}
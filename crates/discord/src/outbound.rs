@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use serenity::all::{ChannelId, CreateAttachment, CreateMessage, Http};
+
+use moltis_channels::chunking::chunk_message;
+use moltis_common::types::ReplyPayload;
+
+/// Discord's per-message text limit.
+const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+/// Send a plain text message to a Discord channel or DM, splitting it across several
+/// messages in order if it exceeds Discord's 2000-char limit.
+pub async fn send_text(http: &Arc<Http>, to: &str, text: &str) -> anyhow::Result<()> {
+    let channel_id = parse_channel_id(to)?;
+    for chunk in chunk_message(text, DISCORD_MESSAGE_LIMIT) {
+        channel_id
+            .send_message(http, CreateMessage::new().content(chunk))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Send a media payload (with optional caption text) to a Discord channel or DM.
+pub async fn send_media(http: &Arc<Http>, to: &str, payload: &ReplyPayload) -> anyhow::Result<()> {
+    let channel_id = parse_channel_id(to)?;
+    let value = serde_json::to_value(payload)?;
+    let caption = value.get("text").and_then(|v| v.as_str()).unwrap_or_default();
+
+    let mut message = CreateMessage::new().content(caption);
+    if let Some(url) = value.get("media_url").and_then(|v| v.as_str()) {
+        message = message.add_file(CreateAttachment::url(http, url).await?);
+    }
+
+    channel_id.send_message(http, message).await?;
+    Ok(())
+}
+
+fn parse_channel_id(to: &str) -> anyhow::Result<ChannelId> {
+    let id = to.parse::<u64>().context("invalid discord channel id")?;
+    Ok(ChannelId::new(id))
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serenity::all::{Client, GatewayIntents, Http};
+use tracing::warn;
+
+use moltis_channels::{ChannelHealthSnapshot, ChannelOutbound, ChannelPlugin, ChannelStatus};
+use moltis_common::types::ReplyPayload;
+
+use crate::{
+    handlers::DiscordHandler,
+    outbound,
+    state::{AccountState, AccountStateMap},
+};
+
+/// [`ChannelPlugin`] backed by serenity, bridging Discord guilds and DMs into the same
+/// pipeline Telegram uses.
+pub struct DiscordPlugin {
+    accounts: AccountStateMap,
+}
+
+impl Default for DiscordPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiscordPlugin {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            accounts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn http_for(&self, account_id: &str) -> anyhow::Result<Arc<Http>> {
+        self.accounts
+            .read()
+            .unwrap()
+            .get(account_id)
+            .map(|state| Arc::clone(&state.http))
+            .ok_or_else(|| anyhow::anyhow!("unknown discord account: {account_id}"))
+    }
+}
+
+#[async_trait]
+impl ChannelPlugin for DiscordPlugin {
+    fn id(&self) -> &str {
+        "discord"
+    }
+
+    fn name(&self) -> &str {
+        "Discord"
+    }
+
+    async fn start_account(&mut self, account_id: &str, config: serde_json::Value) -> anyhow::Result<()> {
+        let token = config
+            .get("token")
+            .and_then(|v| v.as_str())
+            .context("missing 'token' in discord config")?
+            .to_string();
+
+        let intents = GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::DIRECT_MESSAGES
+            | GatewayIntents::MESSAGE_CONTENT;
+
+        let handler = DiscordHandler {
+            account_id: account_id.to_string(),
+            accounts: Arc::clone(&self.accounts),
+        };
+
+        let mut client = Client::builder(&token, intents)
+            .event_handler(handler)
+            .await
+            .context("failed to build Discord client")?;
+
+        let http = Arc::clone(&client.http);
+        let shard_manager = Arc::clone(&client.shard_manager);
+
+        let join = tokio::spawn(async move {
+            if let Err(e) = client.start().await {
+                warn!(error = %e, "discord client stopped with error");
+            }
+        });
+
+        self.accounts.write().unwrap().insert(
+            account_id.to_string(),
+            AccountState {
+                config,
+                http,
+                shard_manager,
+                join,
+                message_log: None,
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn stop_account(&mut self, account_id: &str) -> anyhow::Result<()> {
+        let state = self.accounts.write().unwrap().remove(account_id);
+        if let Some(state) = state {
+            state.shard_manager.shutdown_all().await;
+            state.join.abort();
+        }
+        Ok(())
+    }
+
+    fn outbound(&self) -> Option<&dyn ChannelOutbound> {
+        Some(self)
+    }
+
+    fn status(&self) -> Option<&dyn ChannelStatus> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ChannelOutbound for DiscordPlugin {
+    async fn send_text(&self, account_id: &str, to: &str, text: &str) -> anyhow::Result<()> {
+        let http = self.http_for(account_id)?;
+        outbound::send_text(&http, to, text).await
+    }
+
+    async fn send_media(&self, account_id: &str, to: &str, payload: &ReplyPayload) -> anyhow::Result<()> {
+        let http = self.http_for(account_id)?;
+        outbound::send_media(&http, to, payload).await
+    }
+}
+
+#[async_trait]
+impl ChannelStatus for DiscordPlugin {
+    async fn probe(&self, account_id: &str) -> anyhow::Result<ChannelHealthSnapshot> {
+        let connected = self.accounts.read().unwrap().contains_key(account_id);
+        Ok(ChannelHealthSnapshot {
+            connected,
+            account_id: account_id.to_string(),
+            details: None,
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serenity::all::{Http, ShardManager};
+use tokio::task::JoinHandle;
+
+use moltis_channels::message_log::MessageLog;
+
+/// Per-account Discord connection state, shared between the plugin's control surface
+/// (`DiscordPlugin`) and the gateway event handler (`DiscordHandler`) it spawns.
+pub struct AccountState {
+    pub config: serde_json::Value,
+    pub http: Arc<Http>,
+    pub shard_manager: Arc<ShardManager>,
+    pub join: JoinHandle<()>,
+    pub message_log: Option<Arc<dyn MessageLog>>,
+}
+
+pub type AccountStateMap = Arc<RwLock<HashMap<String, AccountState>>>;
@@ -1,16 +1,19 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use {
     async_trait::async_trait,
+    moltis_common::types::ReplyPayload,
     serde_json::Value,
     tokio::sync::RwLock,
     tracing::{error, info, warn},
 };
 
-use {moltis_channels::ChannelPlugin, moltis_telegram::TelegramPlugin};
+use moltis_channels::ChannelPlugin;
 
 use moltis_channels::store::{ChannelStore, StoredChannel};
 
+use crate::channel_stream::ChannelEventBus;
 use crate::services::{ChannelService, ServiceResult};
 
 fn unix_now() -> i64 {
@@ -20,54 +23,163 @@ fn unix_now() -> i64 {
         .as_secs() as i64
 }
 
-/// Live channel service backed by `TelegramPlugin`.
+/// What the service remembers about one account, enough to route `remove`/`update` and
+/// to render `status()` without the `ChannelPlugin` trait needing its own account
+/// enumeration.
+#[derive(Clone)]
+struct AccountEntry {
+    channel_type: String,
+    config: Value,
+}
+
+/// Channel service backed by a registry of [`ChannelPlugin`] backends keyed by
+/// `channel_type` (`"telegram"`, `"discord"`, ...). `add`/`remove`/`update` dispatch to
+/// whichever backend owns the account's type instead of hard-coding Telegram, so the
+/// same surface drives any registered platform.
 pub struct LiveChannelService {
-    telegram: Arc<RwLock<TelegramPlugin>>,
+    plugins: HashMap<String, Arc<RwLock<Box<dyn ChannelPlugin>>>>,
     store: Arc<dyn ChannelStore>,
+    accounts: RwLock<HashMap<String, AccountEntry>>,
+    /// Fan-out bus for inbound messages; `None` until [`Self::with_event_bus`]
+    /// attaches one.
+    event_bus: Option<Arc<ChannelEventBus>>,
 }
 
 impl LiveChannelService {
-    pub fn new(telegram: TelegramPlugin, store: Arc<dyn ChannelStore>) -> Self {
+    /// Build the service from a registry of backends keyed by `channel_type`.
+    pub fn new(plugins: HashMap<String, Box<dyn ChannelPlugin>>, store: Arc<dyn ChannelStore>) -> Self {
         Self {
-            telegram: Arc::new(RwLock::new(telegram)),
+            plugins: plugins
+                .into_iter()
+                .map(|(k, v)| (k, Arc::new(RwLock::new(v))))
+                .collect(),
             store,
+            accounts: RwLock::new(HashMap::new()),
+            event_bus: None,
+        }
+    }
+
+    /// Attach an inbound event bus: every registered plugin's inbound stream is
+    /// drained into it, and [`Self::event_bus`] exposes it for the gateway's WebSocket
+    /// route to hand out per-connection [`crate::channel_stream::StreamManager`]s.
+    pub fn with_event_bus(self, event_bus: Arc<ChannelEventBus>) -> Self {
+        for plugin in self.plugins.values() {
+            event_bus.attach_plugin(Arc::clone(plugin));
+        }
+        Self {
+            event_bus: Some(event_bus),
+            ..self
         }
     }
+
+    /// The inbound event bus, if one was attached via [`Self::with_event_bus`].
+    #[must_use]
+    pub fn event_bus(&self) -> Option<&Arc<ChannelEventBus>> {
+        self.event_bus.as_ref()
+    }
+
+    /// Reconnect every account persisted in the store, restoring each to the plugin
+    /// registered for its `channel_type`. Call once at startup; an account whose
+    /// plugin isn't registered (or that fails to start) is logged and skipped rather
+    /// than aborting the rest.
+    pub async fn restore(&self) -> anyhow::Result<()> {
+        for stored in self.store.list_all().await? {
+            let Some(plugin) = self.plugins.get(&stored.channel_type) else {
+                warn!(
+                    account_id = %stored.account_id,
+                    channel_type = %stored.channel_type,
+                    "no plugin registered for stored channel type, skipping restore"
+                );
+                continue;
+            };
+
+            if let Err(e) = plugin
+                .write()
+                .await
+                .start_account(&stored.account_id, stored.config.clone())
+                .await
+            {
+                warn!(
+                    account_id = %stored.account_id,
+                    channel_type = %stored.channel_type,
+                    error = %e,
+                    "failed to restore channel account"
+                );
+                continue;
+            }
+
+            self.accounts.write().await.insert(
+                stored.account_id.clone(),
+                AccountEntry {
+                    channel_type: stored.channel_type.clone(),
+                    config: stored.config,
+                },
+            );
+            info!(account_id = %stored.account_id, channel_type = %stored.channel_type, "restored channel account");
+        }
+        Ok(())
+    }
+
+    fn plugin_for(&self, channel_type: &str) -> Result<Arc<RwLock<Box<dyn ChannelPlugin>>>, String> {
+        self.plugins
+            .get(channel_type)
+            .cloned()
+            .ok_or_else(|| format!("unsupported channel type: {channel_type}"))
+    }
+
+    async fn channel_type_of(&self, account_id: &str) -> Result<String, String> {
+        self.accounts
+            .read()
+            .await
+            .get(account_id)
+            .map(|entry| entry.channel_type.clone())
+            .ok_or_else(|| format!("unknown channel account: {account_id}"))
+    }
 }
 
 #[async_trait]
 impl ChannelService for LiveChannelService {
     async fn status(&self) -> ServiceResult {
-        let tg = self.telegram.read().await;
-        let account_ids = tg.account_ids();
+        let accounts = self.accounts.read().await.clone();
         let mut channels = Vec::new();
 
-        if let Some(status) = tg.status() {
-            for aid in &account_ids {
-                match status.probe(aid).await {
-                    Ok(snap) => {
-                        let mut entry = serde_json::json!({
-                            "type": "telegram",
-                            "name": format!("Telegram ({})", aid),
-                            "account_id": aid,
-                            "status": if snap.connected { "connected" } else { "disconnected" },
-                            "details": snap.details,
-                        });
-                        if let Some(cfg) = tg.account_config(aid) {
-                            entry["config"] = cfg;
-                        }
-                        channels.push(entry);
-                    },
-                    Err(e) => {
-                        channels.push(serde_json::json!({
-                            "type": "telegram",
-                            "name": format!("Telegram ({})", aid),
-                            "account_id": aid,
-                            "status": "error",
-                            "details": e.to_string(),
-                        }));
-                    },
-                }
+        for (account_id, entry) in &accounts {
+            let Some(plugin) = self.plugins.get(&entry.channel_type) else {
+                continue;
+            };
+            let guard = plugin.read().await;
+
+            let Some(status) = guard.status() else {
+                channels.push(serde_json::json!({
+                    "type": entry.channel_type,
+                    "name": format!("{} ({})", guard.name(), account_id),
+                    "account_id": account_id,
+                    "status": "unknown",
+                    "config": entry.config,
+                }));
+                continue;
+            };
+
+            match status.probe(account_id).await {
+                Ok(snap) => {
+                    channels.push(serde_json::json!({
+                        "type": entry.channel_type,
+                        "name": format!("{} ({})", guard.name(), account_id),
+                        "account_id": account_id,
+                        "status": if snap.connected { "connected" } else { "disconnected" },
+                        "details": snap.details,
+                        "config": entry.config,
+                    }));
+                },
+                Err(e) => {
+                    channels.push(serde_json::json!({
+                        "type": entry.channel_type,
+                        "name": format!("{} ({})", guard.name(), account_id),
+                        "account_id": account_id,
+                        "status": "error",
+                        "details": e.to_string(),
+                    }));
+                },
             }
         }
 
@@ -78,38 +190,44 @@ impl ChannelService for LiveChannelService {
         let channel_type = params
             .get("type")
             .and_then(|v| v.as_str())
-            .unwrap_or("telegram");
-
-        if channel_type != "telegram" {
-            return Err(format!("unsupported channel type: {channel_type}"));
-        }
+            .ok_or_else(|| "missing 'type' parameter".to_string())?
+            .to_string();
 
         let account_id = params
             .get("account_id")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "missing 'account_id'".to_string())?;
+            .ok_or_else(|| "missing 'account_id'".to_string())?
+            .to_string();
 
         let config = params
             .get("config")
             .cloned()
             .unwrap_or(Value::Object(Default::default()));
 
-        info!(account_id, "adding telegram channel account");
+        let plugin = self.plugin_for(&channel_type)?;
 
-        let mut tg = self.telegram.write().await;
-        tg.start_account(account_id, config.clone())
-            .await
-            .map_err(|e| {
-                error!(error = %e, account_id, "failed to start telegram account");
-                e.to_string()
-            })?;
+        info!(account_id, channel_type, "adding channel account");
+
+        if let Err(e) = plugin.write().await.start_account(&account_id, config.clone()).await {
+            error!(error = %e, account_id, channel_type, "failed to start channel account");
+            record_channel_outcome("add", &channel_type, &account_id, false);
+            return Err(e.to_string());
+        }
+
+        self.accounts.write().await.insert(
+            account_id.clone(),
+            AccountEntry {
+                channel_type: channel_type.clone(),
+                config: config.clone(),
+            },
+        );
 
         let now = unix_now();
         if let Err(e) = self
             .store
             .upsert(StoredChannel {
-                account_id: account_id.to_string(),
-                channel_type: "telegram".into(),
+                account_id: account_id.clone(),
+                channel_type: channel_type.clone(),
                 config,
                 created_at: now,
                 updated_at: now,
@@ -119,6 +237,7 @@ impl ChannelService for LiveChannelService {
             warn!(error = %e, account_id, "failed to persist channel");
         }
 
+        record_channel_outcome("add", &channel_type, &account_id, true);
         Ok(serde_json::json!({ "added": account_id }))
     }
 
@@ -126,20 +245,27 @@ impl ChannelService for LiveChannelService {
         let account_id = params
             .get("account_id")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "missing 'account_id'".to_string())?;
+            .ok_or_else(|| "missing 'account_id'".to_string())?
+            .to_string();
 
-        info!(account_id, "removing telegram channel account");
+        let channel_type = self.channel_type_of(&account_id).await?;
+        let plugin = self.plugin_for(&channel_type)?;
 
-        let mut tg = self.telegram.write().await;
-        tg.stop_account(account_id).await.map_err(|e| {
-            error!(error = %e, account_id, "failed to stop telegram account");
-            e.to_string()
-        })?;
+        info!(account_id, channel_type, "removing channel account");
 
-        if let Err(e) = self.store.delete(account_id).await {
+        if let Err(e) = plugin.write().await.stop_account(&account_id).await {
+            error!(error = %e, account_id, channel_type, "failed to stop channel account");
+            record_channel_outcome("remove", &channel_type, &account_id, false);
+            return Err(e.to_string());
+        }
+
+        self.accounts.write().await.remove(&account_id);
+
+        if let Err(e) = self.store.delete(&account_id).await {
             warn!(error = %e, account_id, "failed to delete channel from store");
         }
 
+        record_channel_outcome("remove", &channel_type, &account_id, true);
         Ok(serde_json::json!({ "removed": account_id }))
     }
 
@@ -151,36 +277,49 @@ impl ChannelService for LiveChannelService {
         let account_id = params
             .get("account_id")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "missing 'account_id'".to_string())?;
+            .ok_or_else(|| "missing 'account_id'".to_string())?
+            .to_string();
 
         let config = params
             .get("config")
             .cloned()
             .ok_or_else(|| "missing 'config'".to_string())?;
 
-        info!(account_id, "updating telegram channel account");
+        let channel_type = self.channel_type_of(&account_id).await?;
+        let plugin = self.plugin_for(&channel_type)?;
 
-        let mut tg = self.telegram.write().await;
+        info!(account_id, channel_type, "updating channel account");
+
+        let mut guard = plugin.write().await;
 
         // Stop then restart with new config
-        tg.stop_account(account_id).await.map_err(|e| {
-            error!(error = %e, account_id, "failed to stop telegram account for update");
-            e.to_string()
-        })?;
+        if let Err(e) = guard.stop_account(&account_id).await {
+            error!(error = %e, account_id, channel_type, "failed to stop channel account for update");
+            record_channel_outcome("update", &channel_type, &account_id, false);
+            return Err(e.to_string());
+        }
 
-        tg.start_account(account_id, config.clone())
-            .await
-            .map_err(|e| {
-                error!(error = %e, account_id, "failed to restart telegram account after update");
-                e.to_string()
-            })?;
+        if let Err(e) = guard.start_account(&account_id, config.clone()).await {
+            error!(error = %e, account_id, channel_type, "failed to restart channel account after update");
+            record_channel_outcome("update", &channel_type, &account_id, false);
+            return Err(e.to_string());
+        }
+        drop(guard);
+
+        self.accounts.write().await.insert(
+            account_id.clone(),
+            AccountEntry {
+                channel_type: channel_type.clone(),
+                config: config.clone(),
+            },
+        );
 
         let now = unix_now();
         if let Err(e) = self
             .store
             .upsert(StoredChannel {
-                account_id: account_id.to_string(),
-                channel_type: "telegram".into(),
+                account_id: account_id.clone(),
+                channel_type: channel_type.clone(),
                 config,
                 created_at: now,
                 updated_at: now,
@@ -190,10 +329,61 @@ impl ChannelService for LiveChannelService {
             warn!(error = %e, account_id, "failed to persist channel update");
         }
 
+        record_channel_outcome("update", &channel_type, &account_id, true);
         Ok(serde_json::json!({ "updated": account_id }))
     }
 
-    async fn send(&self, _params: Value) -> ServiceResult {
-        Err("direct channel send not yet implemented".into())
+    async fn send(&self, params: Value) -> ServiceResult {
+        let account_id = params
+            .get("account_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "missing 'account_id'".to_string())?;
+        let to = params
+            .get("to")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "missing 'to'".to_string())?;
+
+        let channel_type = self.channel_type_of(account_id).await?;
+        let plugin = self.plugin_for(&channel_type)?;
+        let guard = plugin.read().await;
+
+        let Some(outbound) = guard.outbound() else {
+            record_channel_outcome("send", &channel_type, account_id, false);
+            return Err(format!("channel type '{channel_type}' does not support sending"));
+        };
+
+        let result = if let Some(media) = params.get("media") {
+            let payload: ReplyPayload =
+                serde_json::from_value(media.clone()).map_err(|e| format!("invalid 'media' payload: {e}"))?;
+            outbound.send_media(account_id, to, &payload).await
+        } else if let Some(text) = params.get("text").and_then(|v| v.as_str()) {
+            outbound.send_text(account_id, to, text).await
+        } else {
+            return Err("missing 'text' or 'media' parameter".to_string());
+        };
+
+        match result {
+            Ok(()) => {
+                record_channel_outcome("send", &channel_type, account_id, true);
+                Ok(serde_json::json!({ "sent": true }))
+            },
+            Err(e) => {
+                record_channel_outcome("send", &channel_type, account_id, false);
+                Err(e.to_string())
+            },
+        }
     }
 }
+
+/// Record a channel operation's outcome, tagged by operation, channel type, and
+/// account, for the `/metrics` endpoint.
+fn record_channel_outcome(operation: &str, channel_type: &str, account_id: &str, success: bool) {
+    metrics::counter!(
+        "channel_operations_total",
+        "operation" => operation.to_string(),
+        "channel_type" => channel_type.to_string(),
+        "account_id" => account_id.to_string(),
+        "outcome" => if success { "success" } else { "failure" },
+    )
+    .increment(1);
+}
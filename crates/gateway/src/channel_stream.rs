@@ -0,0 +1,191 @@
+//! Bidirectional channel messaging: fan-out of inbound events to WebSocket subscribers.
+//!
+//! Modeled on flodgatt's streaming design: every registered plugin's inbound stream is
+//! drained into one shared `broadcast` bus, and each WebSocket connection gets its own
+//! [`StreamManager`] that subscribes to the bus and filters events down to what that
+//! client asked for. An optional Redis pub/sub backend lets several daemon processes
+//! share one event bus instead of each only seeing the plugins running in its own
+//! process.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::{debug, warn};
+
+use moltis_channels::plugin::{ChannelPlugin, InboundMessage};
+
+const CHANNEL_CAPACITY: usize = 1024;
+const REDIS_PUBSUB_CHANNEL: &str = "moltis:channel-events";
+const REDIS_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Shared inbound event bus: plugins publish here, WebSocket connections subscribe.
+pub struct ChannelEventBus {
+    sender: broadcast::Sender<InboundMessage>,
+    redis: Option<Arc<Mutex<redis::aio::MultiplexedConnection>>>,
+}
+
+impl ChannelEventBus {
+    /// Build a local-only event bus, for a single daemon process.
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Arc::new(Self { sender, redis: None })
+    }
+
+    /// Build an event bus backed by Redis pub/sub, so events published by any daemon
+    /// process sharing `redis_url` reach every process's subscribers.
+    pub async fn with_redis(redis_url: &str) -> anyhow::Result<Arc<Self>> {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let client = redis::Client::open(redis_url)?;
+        let conn = client.get_multiplexed_async_connection().await?;
+
+        let bus = Arc::new(Self {
+            sender,
+            redis: Some(Arc::new(Mutex::new(conn))),
+        });
+
+        bus.clone().spawn_redis_subscriber(redis_url.to_string());
+        Ok(bus)
+    }
+
+    fn spawn_redis_subscriber(self: Arc<Self>, redis_url: String) {
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = self.run_redis_subscriber(&redis_url).await {
+                    warn!(error = %e, "channel event Redis subscriber failed, reconnecting");
+                }
+                tokio::time::sleep(REDIS_RECONNECT_DELAY).await;
+            }
+        });
+    }
+
+    async fn run_redis_subscriber(&self, redis_url: &str) -> anyhow::Result<()> {
+        use futures_util::StreamExt;
+
+        let client = redis::Client::open(redis_url)?;
+        let mut pubsub = client.get_async_pubsub().await?;
+        pubsub.subscribe(REDIS_PUBSUB_CHANNEL).await?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = match msg.get_payload() {
+                Ok(p) => p,
+                Err(e) => {
+                    warn!(error = %e, "failed to read Redis channel event payload");
+                    continue;
+                },
+            };
+            match serde_json::from_str::<InboundMessage>(&payload) {
+                Ok(event) => {
+                    let _ = self.sender.send(event);
+                },
+                Err(e) => warn!(error = %e, "failed to decode relayed channel event"),
+            }
+        }
+
+        anyhow::bail!("Redis pub/sub stream ended")
+    }
+
+    /// Publish an inbound message to every local subscriber, and to peer daemons via
+    /// Redis if configured.
+    pub async fn publish(&self, event: InboundMessage) {
+        let _ = self.sender.send(event.clone());
+
+        if let Some(redis) = &self.redis {
+            match serde_json::to_string(&event) {
+                Ok(payload) => {
+                    let mut conn = redis.lock().await;
+                    if let Err(e) = redis::AsyncCommands::publish::<_, _, ()>(
+                        &mut *conn,
+                        REDIS_PUBSUB_CHANNEL,
+                        payload,
+                    )
+                    .await
+                    {
+                        warn!(error = %e, "failed to publish channel event to Redis");
+                    }
+                },
+                Err(e) => warn!(error = %e, "failed to serialize channel event for Redis"),
+            }
+        }
+    }
+
+    /// Drain `plugin`'s inbound stream into this bus for the lifetime of the process.
+    /// A plugin with no inbound adapter ([`ChannelPlugin::inbound`] returning `None`)
+    /// is a no-op.
+    pub fn attach_plugin(self: &Arc<Self>, plugin: Arc<RwLock<Box<dyn ChannelPlugin>>>) {
+        let bus = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut receiver = {
+                let guard = plugin.read().await;
+                let Some(inbound) = guard.inbound() else {
+                    return;
+                };
+                inbound.subscribe().await
+            };
+
+            while let Some(event) = receiver.recv().await {
+                bus.publish(event).await;
+            }
+        });
+    }
+
+    /// Open a per-connection subscription, filtered down to what that client asked for.
+    #[must_use]
+    pub fn stream_manager(self: &Arc<Self>, filter: StreamFilter) -> StreamManager {
+        StreamManager {
+            receiver: self.sender.subscribe(),
+            filter,
+        }
+    }
+}
+
+/// What a single WebSocket subscriber wants to see, parsed from its connection query
+/// params.
+#[derive(Debug, Clone, Default)]
+pub struct StreamFilter {
+    pub channel_type: Option<String>,
+    pub account_id: Option<String>,
+}
+
+impl StreamFilter {
+    /// Build a filter from gateway WebSocket query params (`channel_type`, `account_id`).
+    #[must_use]
+    pub fn from_params(params: &serde_json::Value) -> Self {
+        Self {
+            channel_type: params.get("channel_type").and_then(|v| v.as_str()).map(str::to_string),
+            account_id: params.get("account_id").and_then(|v| v.as_str()).map(str::to_string),
+        }
+    }
+
+    fn matches(&self, event: &InboundMessage) -> bool {
+        self.channel_type.as_deref().map_or(true, |t| t == event.channel_type)
+            && self.account_id.as_deref().map_or(true, |a| a == event.account_id)
+    }
+}
+
+/// Per-connection view of the shared event bus: the same inbound events, filtered to
+/// what this subscriber asked for.
+pub struct StreamManager {
+    receiver: broadcast::Receiver<InboundMessage>,
+    filter: StreamFilter,
+}
+
+impl StreamManager {
+    /// Wait for the next event matching this subscriber's filter, skipping others.
+    /// Returns `None` once the bus is gone (all publishers dropped).
+    pub async fn next(&mut self) -> Option<InboundMessage> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!(skipped, "channel event stream subscriber lagged, dropping missed events");
+                    continue;
+                },
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -6,12 +6,15 @@ use serde_json::Value;
 use tokio::task::AbortHandle;
 use tokio::sync::RwLock;
 use tokio_stream::StreamExt;
-use tracing::{debug, warn};
+use tracing::{debug, warn, Instrument};
 
 use moltis_agents::model::StreamEvent;
 use moltis_agents::providers::ProviderRegistry;
+use moltis_skills::{hooks, types::SkillMetadata};
 
 use crate::broadcast::{broadcast, BroadcastOpts};
+use crate::federation::FederationClient;
+use crate::history::{HistoryEntry, HistorySelector, HistoryStore};
 use crate::services::{ChatService, ModelService, ServiceResult};
 use crate::state::GatewayState;
 
@@ -52,6 +55,13 @@ pub struct LiveChatService {
     providers: Arc<ProviderRegistry>,
     state: Arc<GatewayState>,
     active_runs: Arc<RwLock<HashMap<String, AbortHandle>>>,
+    history: Arc<HistoryStore>,
+    /// Skills enabled for this service, in manifest order. Those declaring `hooks` act
+    /// as active middleware around `send` rather than passive prompt text.
+    enabled_skills: Arc<RwLock<Vec<SkillMetadata>>>,
+    /// When set, this node is part of a cluster: broadcast events are relayed to peers
+    /// and session ownership is recorded in the shared routing table.
+    federation: Option<Arc<FederationClient>>,
 }
 
 impl LiveChatService {
@@ -60,6 +70,25 @@ impl LiveChatService {
             providers,
             state,
             active_runs: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(HistoryStore::new()),
+            enabled_skills: Arc::new(RwLock::new(Vec::new())),
+            federation: None,
+        }
+    }
+
+    /// Set the skills whose hooks should run around every `send`, in manifest order.
+    pub fn with_skills(self, skills: Vec<SkillMetadata>) -> Self {
+        Self {
+            enabled_skills: Arc::new(RwLock::new(skills)),
+            ..self
+        }
+    }
+
+    /// Join this service to a cluster: its broadcasts are relayed to peer nodes.
+    pub fn with_federation(self, federation: Arc<FederationClient>) -> Self {
+        Self {
+            federation: Some(federation),
+            ..self
         }
     }
 }
@@ -67,6 +96,26 @@ impl LiveChatService {
 #[async_trait]
 impl ChatService for LiveChatService {
     async fn send(&self, params: Value) -> ServiceResult {
+        let session_key = params
+            .get("key")
+            .and_then(|v| v.as_str())
+            .unwrap_or("default")
+            .to_string();
+
+        // If another node in the cluster already owns this session's active run,
+        // proxy the request there instead of starting a second, competing run here.
+        if let Some(fed) = &self.federation {
+            if let Some(owner) = fed.routing.owner_of(&session_key).await {
+                if owner != fed.self_node_id() {
+                    debug!(session_key = %session_key, %owner, "proxying chat.send to owning node");
+                    return fed
+                        .forward_chat_send(&owner, &params)
+                        .await
+                        .map_err(|e| format!("failed to proxy chat.send to owner node '{owner}': {e}"));
+                }
+            }
+        }
+
         let text = params
             .get("text")
             .and_then(|v| v.as_str())
@@ -92,75 +141,201 @@ impl ChatService for LiveChatService {
         };
 
         let run_id = uuid::Uuid::new_v4().to_string();
+
+        // Persist the user's own message as soon as the send is accepted, so a
+        // reconnecting client can replay what it asked in addition to what it got back.
+        let user_entry = HistoryEntry {
+            run_id: run_id.clone(),
+            role: "user".into(),
+            text: text.clone(),
+            model: None,
+            timestamp_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64,
+        };
+        if let Err(e) = self.history.append(&session_key, user_entry).await {
+            warn!(run_id = %run_id, error = %e, "failed to persist user chat history entry");
+        }
+
+        // Run each enabled skill's `before_send` hook, in manifest order. Any hook may
+        // rewrite the outgoing messages for the next hook, or veto the send outright.
+        let mut messages = vec![serde_json::json!({
+            "role": "user",
+            "content": text,
+        })];
+        let skills = self.enabled_skills.read().await.clone();
+        for skill in &skills {
+            let Some(command) = &skill.hooks.before_send else {
+                continue;
+            };
+            match hooks::run_before_send(command, &messages).await {
+                hooks::BeforeSendOutcome::Continue { messages: rewritten } => {
+                    messages = rewritten;
+                },
+                hooks::BeforeSendOutcome::Block { reason } => {
+                    warn!(skill = %skill.name, %reason, "send vetoed by before_send hook");
+                    let event = serde_json::json!({
+                        "runId": run_id,
+                        "state": "blocked",
+                        "skill": skill.name,
+                        "reason": reason,
+                    });
+                    broadcast(&self.state, "chat", event.clone(), BroadcastOpts::default()).await;
+                    if let Some(fed) = &self.federation {
+                        fed.relay("chat", event, Some(&session_key));
+                    }
+                    return Ok(serde_json::json!({ "runId": run_id, "blocked": true }));
+                },
+            }
+        }
+
         let state = Arc::clone(&self.state);
         let active_runs = Arc::clone(&self.active_runs);
+        let history = Arc::clone(&self.history);
+        let federation = self.federation.clone();
         let run_id_clone = run_id.clone();
+        let model_id = provider.id().to_string();
+
+        if let Some(fed) = &federation {
+            let self_id = fed.self_node_id().to_string();
+            fed.routing.set_owner(&session_key, &self_id).await;
+        }
 
-        let handle = tokio::spawn(async move {
-            let messages = vec![serde_json::json!({
-                "role": "user",
-                "content": text,
-            })];
+        let run_span = tracing::info_span!(
+            "chat.send",
+            run_id = %run_id,
+            model = %model_id,
+            input_tokens = tracing::field::Empty,
+            output_tokens = tracing::field::Empty,
+        );
 
-            let mut stream = provider.stream(messages);
+        let handle = tokio::spawn(
+            async move {
+            let mut stream = provider.stream(messages, &[]);
             let mut accumulated = String::new();
 
             while let Some(event) = stream.next().await {
                 match event {
                     StreamEvent::Delta(delta) => {
                         accumulated.push_str(&delta);
-                        broadcast(
-                            &state,
-                            "chat",
-                            serde_json::json!({
-                                "runId": run_id_clone,
-                                "state": "delta",
-                                "text": delta,
-                            }),
-                            BroadcastOpts::default(),
-                        )
-                        .await;
+                        let delta_span = tracing::debug_span!("chat.delta", run_id = %run_id_clone, len = delta.len());
+                        let event = serde_json::json!({
+                            "runId": run_id_clone,
+                            "state": "delta",
+                            "text": delta,
+                        });
+                        broadcast(&state, "chat", event.clone(), BroadcastOpts::default())
+                            .instrument(delta_span)
+                            .await;
+                        if let Some(fed) = &federation {
+                            fed.relay("chat", event, Some(&session_key));
+                        }
+                    }
+                    StreamEvent::ToolCalls(calls) => {
+                        debug!(run_id = %run_id_clone, count = calls.len(), "chat stream requested tool calls");
+                        let event = serde_json::json!({
+                            "runId": run_id_clone,
+                            "state": "toolCalls",
+                            "toolCalls": calls.iter().map(|c| serde_json::json!({
+                                "id": c.id,
+                                "name": c.name,
+                                "arguments": c.arguments,
+                            })).collect::<Vec<_>>(),
+                        });
+                        broadcast(&state, "chat", event.clone(), BroadcastOpts::default()).await;
+                        if let Some(fed) = &federation {
+                            fed.relay("chat", event, Some(&session_key));
+                        }
+                    }
+                    StreamEvent::ToolCall { id, name, arguments } => {
+                        debug!(run_id = %run_id_clone, %name, "chat stream requested a tool call");
+                        let event = serde_json::json!({
+                            "runId": run_id_clone,
+                            "state": "toolCall",
+                            "toolCall": { "id": id, "name": name, "arguments": arguments },
+                        });
+                        broadcast(&state, "chat", event.clone(), BroadcastOpts::default()).await;
+                        if let Some(fed) = &federation {
+                            fed.relay("chat", event, Some(&session_key));
+                        }
                     }
                     StreamEvent::Done(usage) => {
+                        let current = tracing::Span::current();
+                        current.record("input_tokens", usage.input_tokens);
+                        current.record("output_tokens", usage.output_tokens);
                         debug!(
                             run_id = %run_id_clone,
                             input_tokens = usage.input_tokens,
                             output_tokens = usage.output_tokens,
                             "chat stream done"
                         );
-                        broadcast(
-                            &state,
-                            "chat",
-                            serde_json::json!({
-                                "runId": run_id_clone,
-                                "state": "final",
-                                "text": accumulated,
-                            }),
-                            BroadcastOpts::default(),
-                        )
-                        .await;
+                        let final_event = serde_json::json!({
+                            "runId": run_id_clone,
+                            "state": "final",
+                            "text": accumulated,
+                        });
+                        broadcast(&state, "chat", final_event.clone(), BroadcastOpts::default()).await;
+                        if let Some(fed) = &federation {
+                            fed.relay("chat", final_event, Some(&session_key));
+                        }
+
+                        let timestamp_ms = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_millis() as i64;
+                        let entry = HistoryEntry {
+                            run_id: run_id_clone.clone(),
+                            role: "assistant".into(),
+                            text: accumulated.clone(),
+                            model: Some(model_id.clone()),
+                            timestamp_ms,
+                        };
+                        if let Err(e) = history.append(&session_key, entry).await {
+                            warn!(run_id = %run_id_clone, error = %e, "failed to persist chat history entry");
+                        }
+
+                        for skill in &skills {
+                            if let Some(command) = &skill.hooks.after_send {
+                                hooks::run_after_send(
+                                    command,
+                                    &accumulated,
+                                    usage.input_tokens,
+                                    usage.output_tokens,
+                                )
+                                .await;
+                            }
+                        }
+
                         break;
                     }
                     StreamEvent::Error(msg) => {
                         warn!(run_id = %run_id_clone, error = %msg, "chat stream error");
-                        broadcast(
-                            &state,
-                            "chat",
-                            serde_json::json!({
-                                "runId": run_id_clone,
-                                "state": "error",
-                                "message": msg,
-                            }),
-                            BroadcastOpts::default(),
-                        )
-                        .await;
+                        let error_event = serde_json::json!({
+                            "runId": run_id_clone,
+                            "state": "error",
+                            "message": msg,
+                        });
+                        broadcast(&state, "chat", error_event.clone(), BroadcastOpts::default()).await;
+                        if let Some(fed) = &federation {
+                            fed.relay("chat", error_event, Some(&session_key));
+                        }
+
+                        for skill in &skills {
+                            if let Some(command) = &skill.hooks.on_error {
+                                hooks::run_on_error(command, &msg).await;
+                            }
+                        }
+
                         break;
                     }
                 }
             }
 
             active_runs.write().await.remove(&run_id_clone);
-        });
+            }
+            .instrument(run_span),
+        );
 
         self.active_runs
             .write()
@@ -182,8 +357,21 @@ impl ChatService for LiveChatService {
         Ok(serde_json::json!({}))
     }
 
-    async fn history(&self, _params: Value) -> ServiceResult {
-        Ok(serde_json::json!([]))
+    async fn history(&self, params: Value) -> ServiceResult {
+        let key = params
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "missing 'key' parameter".to_string())?;
+
+        let selector = HistorySelector::from_params(&params)?;
+
+        let entries = self
+            .history
+            .query(key, &selector)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(serde_json::json!(entries))
     }
 
     async fn inject(&self, _params: Value) -> ServiceResult {
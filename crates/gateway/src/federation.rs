@@ -0,0 +1,240 @@
+//! Multi-node federation for chat broadcast.
+//!
+//! Lets several moltis gateways form a cluster: chat delta/final/error events that would
+//! otherwise only reach locally-connected subscribers are relayed to peer nodes so a
+//! user's sessions stay live across devices pointed at different gateways. A read-only
+//! routing table tracks which node owns each session key, so a `chat.send` landing on a
+//! non-owner node can be proxied to the node actually holding the run's `AbortHandle`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// One peer gateway in the cluster.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PeerConfig {
+    pub id: String,
+    pub url: String,
+}
+
+/// Cluster-wide federation config: this node's id plus its known peers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClusterConfig {
+    pub self_node_id: String,
+    #[serde(default)]
+    pub peers: Vec<PeerConfig>,
+}
+
+/// A relayed broadcast event, tagged with the node it originated from so peers can
+/// detect and drop events that have already looped back to their source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayedEvent {
+    pub origin_node_id: String,
+    pub topic: String,
+    pub payload: serde_json::Value,
+    /// Session key this event belongs to, if any. Carried so a receiving node can
+    /// record `origin_node_id` as the session's owner in its own routing table.
+    #[serde(default)]
+    pub session_key: Option<String>,
+}
+
+/// Read-only routing table mapping session keys to the node that owns their active run.
+#[derive(Default)]
+pub struct RoutingTable {
+    owners: RwLock<HashMap<String, String>>,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record (or update) which node owns `session_key`.
+    pub async fn set_owner(&self, session_key: &str, node_id: &str) {
+        self.owners
+            .write()
+            .await
+            .insert(session_key.to_string(), node_id.to_string());
+    }
+
+    /// Look up the node that owns `session_key`, if known.
+    pub async fn owner_of(&self, session_key: &str) -> Option<String> {
+        self.owners.read().await.get(session_key).cloned()
+    }
+}
+
+/// Forwards local broadcast events to peer gateways and relays inbound ones, with
+/// per-peer reconnect/backoff so a down peer doesn't block or crash the local node.
+pub struct FederationClient {
+    self_node_id: String,
+    peers: Vec<PeerConfig>,
+    http: reqwest::Client,
+    pub routing: RoutingTable,
+}
+
+impl FederationClient {
+    pub fn new(config: ClusterConfig) -> Arc<Self> {
+        Arc::new(Self {
+            self_node_id: config.self_node_id,
+            peers: config.peers,
+            http: reqwest::Client::new(),
+            routing: RoutingTable::new(),
+        })
+    }
+
+    /// This node's id, as recorded in sessions this node takes ownership of.
+    pub fn self_node_id(&self) -> &str {
+        &self.self_node_id
+    }
+
+    /// Relay a locally-originated broadcast event to every configured peer, tagging it
+    /// with `session_key` (if any) so peers can learn this node owns that session.
+    ///
+    /// Fire-and-forget per peer: failures are logged and retried with exponential
+    /// backoff in the background rather than blocking the caller's broadcast path.
+    pub fn relay(self: &Arc<Self>, topic: &str, payload: serde_json::Value, session_key: Option<&str>) {
+        if self.peers.is_empty() {
+            return;
+        }
+
+        let event = RelayedEvent {
+            origin_node_id: self.self_node_id.clone(),
+            topic: topic.to_string(),
+            payload,
+            session_key: session_key.map(str::to_string),
+        };
+
+        for peer in &self.peers {
+            let this = Arc::clone(self);
+            let peer = peer.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                this.send_with_backoff(&peer, &event).await;
+            });
+        }
+    }
+
+    /// Handle an event relayed from a peer: drop it if it originated from this node
+    /// (loop prevention) or has already round-tripped back to us.
+    pub fn should_accept_relayed(&self, event: &RelayedEvent) -> bool {
+        event.origin_node_id != self.self_node_id
+    }
+
+    /// Look up `peer_id`'s URL among configured peers.
+    fn peer_url(&self, peer_id: &str) -> Option<&str> {
+        self.peers.iter().find(|p| p.id == peer_id).map(|p| p.url.as_str())
+    }
+
+    /// Proxy a `chat.send` call (its raw JSON-RPC params) to `node_id`, the node whose
+    /// `active_runs` holds the session's `AbortHandle`. Used by [`crate::chat::LiveChatService`]
+    /// when `routing.owner_of` resolves to a node other than this one, so the request
+    /// reaches the owner instead of starting a second, competing run locally.
+    pub async fn forward_chat_send(&self, node_id: &str, params: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let peer_url = self
+            .peer_url(node_id)
+            .ok_or_else(|| anyhow::anyhow!("unknown peer node '{node_id}'"))?;
+        let url = format!("{}/federation/forward/chat.send", peer_url.trim_end_matches('/'));
+        let resp = self.http.post(&url).json(params).send().await?.error_for_status()?;
+        Ok(resp.json::<serde_json::Value>().await?)
+    }
+
+    /// Handle an inbound `POST /federation/relay` body: drop the event if it looped
+    /// back to its origin, otherwise record ownership (if the event carries a
+    /// `session_key`) and re-broadcast it to this node's own locally-connected
+    /// subscribers.
+    pub async fn handle_relay(&self, state: &Arc<crate::state::GatewayState>, event: RelayedEvent) {
+        if !self.should_accept_relayed(&event) {
+            debug!(topic = %event.topic, "dropping relayed event that looped back to its origin");
+            return;
+        }
+
+        if let Some(session_key) = &event.session_key {
+            self.routing.set_owner(session_key, &event.origin_node_id).await;
+        }
+
+        crate::broadcast::broadcast(state, &event.topic, event.payload, crate::broadcast::BroadcastOpts::default()).await;
+    }
+
+    async fn send_with_backoff(&self, peer: &PeerConfig, event: &RelayedEvent) {
+        let mut backoff = Duration::from_millis(200);
+        const MAX_ATTEMPTS: u32 = 5;
+        const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let url = format!("{}/federation/relay", peer.url.trim_end_matches('/'));
+            match self.http.post(&url).json(event).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    debug!(peer = %peer.id, topic = %event.topic, "relayed broadcast to peer");
+                    return;
+                },
+                Ok(resp) => {
+                    warn!(peer = %peer.id, status = %resp.status(), attempt, "peer rejected relayed event");
+                },
+                Err(e) => {
+                    warn!(peer = %peer.id, error = %e, attempt, "failed to reach peer for relay");
+                },
+            }
+
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+
+        warn!(peer = %peer.id, topic = %event.topic, "giving up relaying event to peer after {MAX_ATTEMPTS} attempts");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_events_that_originated_locally() {
+        let client = FederationClient::new(ClusterConfig {
+            self_node_id: "node-a".into(),
+            peers: vec![],
+        });
+        let event = RelayedEvent {
+            origin_node_id: "node-a".into(),
+            topic: "chat".into(),
+            payload: serde_json::json!({}),
+            session_key: None,
+        };
+        assert!(!client.should_accept_relayed(&event));
+    }
+
+    #[test]
+    fn accepts_events_from_other_nodes() {
+        let client = FederationClient::new(ClusterConfig {
+            self_node_id: "node-a".into(),
+            peers: vec![],
+        });
+        let event = RelayedEvent {
+            origin_node_id: "node-b".into(),
+            topic: "chat".into(),
+            payload: serde_json::json!({}),
+            session_key: None,
+        };
+        assert!(client.should_accept_relayed(&event));
+    }
+
+    #[tokio::test]
+    async fn routing_table_tracks_latest_owner() {
+        let table = RoutingTable::new();
+        table.set_owner("telegram:bot1:dm:42", "node-a").await;
+        assert_eq!(
+            table.owner_of("telegram:bot1:dm:42").await,
+            Some("node-a".to_string())
+        );
+        table.set_owner("telegram:bot1:dm:42", "node-b").await;
+        assert_eq!(
+            table.owner_of("telegram:bot1:dm:42").await,
+            Some("node-b".to_string())
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Persistent per-session chat history, queryable with IRCv3 CHATHISTORY-style selectors.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// One completed chat turn persisted to a session's history log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub run_id: String,
+    pub role: String,
+    pub text: String,
+    pub model: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp_ms: i64,
+}
+
+/// Default page size for `history()` queries when the caller doesn't specify one.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+/// Hard ceiling on page size regardless of what the caller asks for.
+pub const MAX_HISTORY_LIMIT: usize = 500;
+
+/// A reference point used by `before`/`after`/`around`/`between` selectors.
+#[derive(Debug, Clone)]
+pub enum HistoryRef {
+    Timestamp(i64),
+    RunId(String),
+}
+
+/// CHATHISTORY-style query selector (see IRCv3 `CHATHISTORY`).
+#[derive(Debug, Clone)]
+pub enum HistorySelector {
+    Latest {
+        limit: usize,
+    },
+    Before {
+        reference: HistoryRef,
+        limit: usize,
+    },
+    After {
+        reference: HistoryRef,
+        limit: usize,
+    },
+    Around {
+        reference: HistoryRef,
+        limit: usize,
+    },
+    Between {
+        from: HistoryRef,
+        to: HistoryRef,
+        limit: usize,
+    },
+}
+
+impl HistorySelector {
+    /// Parse a selector out of a JSON-RPC params object.
+    ///
+    /// Expected shape: `{"mode": "latest"|"before"|"after"|"around"|"between", "limit": n,
+    /// "reference": <ts|runId>, "from": <ts|runId>, "to": <ts|runId>}`. `reference`/`from`/`to`
+    /// accept either a millisecond timestamp or a `runId` string.
+    pub fn from_params(params: &serde_json::Value) -> Result<Self, String> {
+        let limit = params
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|n| (n as usize).min(MAX_HISTORY_LIMIT))
+            .unwrap_or(DEFAULT_HISTORY_LIMIT);
+
+        let mode = params
+            .get("mode")
+            .and_then(|v| v.as_str())
+            .unwrap_or("latest");
+
+        let reference_at = |field: &str| -> Result<HistoryRef, String> {
+            let v = params
+                .get(field)
+                .ok_or_else(|| format!("mode '{mode}' requires a '{field}' reference"))?;
+            parse_reference(v)
+        };
+
+        match mode {
+            "latest" => Ok(Self::Latest { limit }),
+            "before" => Ok(Self::Before {
+                reference: reference_at("reference")?,
+                limit,
+            }),
+            "after" => Ok(Self::After {
+                reference: reference_at("reference")?,
+                limit,
+            }),
+            "around" => Ok(Self::Around {
+                reference: reference_at("reference")?,
+                limit,
+            }),
+            "between" => Ok(Self::Between {
+                from: reference_at("from")?,
+                to: reference_at("to")?,
+                limit,
+            }),
+            other => Err(format!("unknown history mode '{other}'")),
+        }
+    }
+}
+
+fn parse_reference(v: &serde_json::Value) -> Result<HistoryRef, String> {
+    if let Some(s) = v.as_str() {
+        return Ok(HistoryRef::RunId(s.to_string()));
+    }
+    if let Some(n) = v.as_i64() {
+        return Ok(HistoryRef::Timestamp(n));
+    }
+    Err("reference must be a millisecond timestamp or a runId string".into())
+}
+
+/// Resolve a `HistoryRef` to an index position within `entries` (sorted ascending by time).
+fn resolve_index(entries: &[HistoryEntry], reference: &HistoryRef) -> Option<usize> {
+    match reference {
+        HistoryRef::Timestamp(ts) => Some(entries.partition_point(|e| e.timestamp_ms < *ts)),
+        HistoryRef::RunId(id) => entries.iter().position(|e| &e.run_id == id),
+    }
+}
+
+/// Append-only, file-backed history log, one file per session key.
+///
+/// Persists as JSON Lines under `~/.moltis/history/<key>.jsonl`, one line per appended
+/// entry (a run's user message when it is sent, and its assistant reply when it completes).
+pub struct HistoryStore {
+    base_dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl HistoryStore {
+    pub fn new() -> Self {
+        let base_dir = directories::BaseDirs::new()
+            .map(|d| d.home_dir().join(".moltis/history"))
+            .unwrap_or_else(|| PathBuf::from(".moltis/history"));
+        Self {
+            base_dir,
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        let safe: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.base_dir.join(format!("{safe}.jsonl"))
+    }
+
+    /// Append one completed turn to the session's log.
+    pub async fn append(&self, key: &str, entry: HistoryEntry) -> anyhow::Result<()> {
+        let _guard = self.lock.lock().await;
+        tokio::fs::create_dir_all(&self.base_dir).await?;
+        let line = serde_json::to_string(&entry)? + "\n";
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path_for(key))
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Load all entries for a session, oldest first.
+    async fn load_all(&self, key: &str) -> anyhow::Result<Vec<HistoryEntry>> {
+        let path = self.path_for(key);
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries: Vec<HistoryEntry> = content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect();
+        entries.sort_by_key(|e| e.timestamp_ms);
+        Ok(entries)
+    }
+
+    /// Query the session's log with a CHATHISTORY-style selector.
+    ///
+    /// Results are always returned in chronological order; a reference that doesn't
+    /// resolve to any entry yields an empty page rather than an error.
+    pub async fn query(
+        &self,
+        key: &str,
+        selector: &HistorySelector,
+    ) -> anyhow::Result<Vec<HistoryEntry>> {
+        let entries = self.load_all(key).await?;
+
+        let page = match selector {
+            HistorySelector::Latest { limit } => {
+                let start = entries.len().saturating_sub(*limit);
+                entries[start..].to_vec()
+            },
+            HistorySelector::Before { reference, limit } => match resolve_index(&entries, reference) {
+                Some(idx) => {
+                    let start = idx.saturating_sub(*limit);
+                    entries[start..idx].to_vec()
+                },
+                None => Vec::new(),
+            },
+            HistorySelector::After { reference, limit } => match resolve_index(&entries, reference) {
+                Some(idx) => {
+                    let start = match reference {
+                        HistoryRef::RunId(_) => idx + 1,
+                        HistoryRef::Timestamp(_) => idx,
+                    };
+                    if start >= entries.len() {
+                        Vec::new()
+                    } else {
+                        let end = (start + limit).min(entries.len());
+                        entries[start..end].to_vec()
+                    }
+                },
+                None => Vec::new(),
+            },
+            HistorySelector::Around { reference, limit } => match resolve_index(&entries, reference) {
+                Some(idx) => {
+                    let half = limit / 2;
+                    let start = idx.saturating_sub(half);
+                    let end = (idx + (limit - half)).min(entries.len());
+                    entries[start..end].to_vec()
+                },
+                None => Vec::new(),
+            },
+            HistorySelector::Between { from, to, limit } => {
+                match (resolve_index(&entries, from), resolve_index(&entries, to)) {
+                    (Some(a), Some(b)) => {
+                        let (lo, hi) = (a.min(b), a.max(b).min(entries.len()));
+                        let slice = &entries[lo..hi];
+                        if slice.len() > *limit {
+                            slice[slice.len() - limit..].to_vec()
+                        } else {
+                            slice.to_vec()
+                        }
+                    },
+                    _ => Vec::new(),
+                }
+            },
+        };
+
+        Ok(page)
+    }
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,30 @@
+//! Opt-in Prometheus metrics for the gateway daemon.
+//!
+//! Modules across the workspace (`moltis_browser`, `moltis_skills`, this crate's own
+//! `channel`) record counters/histograms via the global `metrics` facade regardless of
+//! whether a recorder is installed — calling [`install_prometheus_recorder`] is what
+//! turns those into a scraped `/metrics` endpoint. Pairs with `init_telemetry`'s OTLP
+//! tracing layer in the CLI: tracing answers "what happened in this run", metrics
+//! answers "how often, and how fast, across all runs".
+
+use anyhow::{Context, Result};
+use metrics_exporter_prometheus::PrometheusBuilder;
+
+/// Install a Prometheus recorder that serves scrapes on `addr` (e.g. `127.0.0.1:9090`).
+///
+/// Must be called at most once per process, before any `metrics::counter!`/`histogram!`
+/// call sites are hit — those silently no-op against the default no-op recorder until
+/// this installs the global one.
+pub fn install_prometheus_recorder(addr: &str) -> Result<()> {
+    let socket_addr: std::net::SocketAddr = addr
+        .parse()
+        .with_context(|| format!("invalid metrics listen address '{addr}'"))?;
+
+    PrometheusBuilder::new()
+        .with_http_listener(socket_addr)
+        .install()
+        .context("failed to install Prometheus metrics recorder")?;
+
+    tracing::info!(addr, "Prometheus metrics exporter listening");
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! TLS termination for the gateway listener.
+//!
+//! Lets the gateway serve HTTPS directly (OAuth callbacks, chat streaming) without
+//! requiring a reverse proxy in front of it. Falls back to plaintext when unconfigured.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use rustls_pemfile::{certs, pkcs8_private_keys};
+use tokio_rustls::rustls::{self, server::WebPkiClientVerifier, RootCertStore};
+
+/// TLS configuration resolved from CLI flags.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// PEM-encoded certificate chain.
+    pub cert_path: Option<String>,
+    /// PEM-encoded private key.
+    pub key_path: Option<String>,
+    /// PEM-encoded CA bundle used to require and verify client certificates (mTLS).
+    pub client_ca_path: Option<String>,
+}
+
+impl TlsOptions {
+    /// `true` when both a cert and key are configured and TLS should be used.
+    pub fn is_enabled(&self) -> bool {
+        self.cert_path.is_some() && self.key_path.is_some()
+    }
+
+    /// Build a rustls server config from the configured paths.
+    ///
+    /// Returns `Ok(None)` when TLS isn't configured, so callers can fall back to plaintext.
+    pub fn build_server_config(&self) -> Result<Option<Arc<rustls::ServerConfig>>> {
+        let (Some(cert_path), Some(key_path)) = (&self.cert_path, &self.key_path) else {
+            return Ok(None);
+        };
+
+        let cert_chain = load_certs(cert_path)?;
+        let key = load_private_key(key_path)?;
+
+        let builder = rustls::ServerConfig::builder();
+
+        let builder = if let Some(ca_path) = &self.client_ca_path {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(ca_path)? {
+                roots
+                    .add(cert)
+                    .context("failed to add client CA certificate to root store")?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .context("failed to build mTLS client verifier")?;
+            builder.with_client_cert_verifier(verifier)
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        let config = builder
+            .with_single_cert(cert_chain, key)
+            .context("invalid TLS certificate/key pair")?;
+
+        Ok(Some(Arc::new(config)))
+    }
+}
+
+fn load_certs(path: &str) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>> {
+    let path = Path::new(path);
+    let contents = std::fs::read(path)
+        .with_context(|| format!("failed to read TLS certificate at {}", path.display()))?;
+    let mut reader = std::io::BufReader::new(contents.as_slice());
+    certs(&mut reader)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("failed to parse PEM certificates from {}", path.display()))
+}
+
+fn load_private_key(path: &str) -> Result<rustls::pki_types::PrivateKeyDer<'static>> {
+    let path = Path::new(path);
+    let contents = std::fs::read(path)
+        .with_context(|| format!("failed to read TLS private key at {}", path.display()))?;
+    let mut reader = std::io::BufReader::new(contents.as_slice());
+    let mut keys = pkcs8_private_keys(&mut reader)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("failed to parse PKCS#8 private key from {}", path.display()))?;
+    let key = keys
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("no private key found in {}", path.display()))?;
+    Ok(rustls::pki_types::PrivateKeyDer::Pkcs8(key))
+}
@@ -0,0 +1,118 @@
+//! Argon2id key derivation and XChaCha20-Poly1305 sealing for at-rest token encryption.
+
+use anyhow::{bail, Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+// 64 MiB memory cost, 3 iterations, 1 degree of parallelism — sane defaults for an
+// interactively-unlocked secret store rather than a high-throughput login endpoint.
+const ARGON2_MEM_KIB: u32 = 64 * 1024;
+const ARGON2_ITERATIONS: u32 = 3;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// A sealed blob: salt + nonce + ciphertext, all base64-encoded for JSON storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedEnvelope {
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let params = Params::new(ARGON2_MEM_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM, Some(32))
+        .map_err(|e| anyhow::anyhow!("invalid Argon2 parameters: {e}"))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Argon2 key derivation failed: {e}"))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` under a key freshly derived from `passphrase`, with a random
+/// salt and nonce. The salt and nonce are carried in the returned envelope so the
+/// ciphertext is self-describing and needs no side-channel state to decrypt later.
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<SealedEnvelope> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XNonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("failed to seal token envelope: {e}"))?;
+
+    Ok(SealedEnvelope {
+        salt: base64_encode(&salt),
+        nonce: base64_encode(&nonce_bytes),
+        ciphertext: base64_encode(&ciphertext),
+    })
+}
+
+/// Decrypt an envelope sealed by [`seal`]. Returns a clear "wrong passphrase / tampered
+/// file" error on AEAD authentication failure rather than leaking the underlying cause.
+pub fn open(passphrase: &str, envelope: &SealedEnvelope) -> Result<Vec<u8>> {
+    let salt = base64_decode(&envelope.salt).context("malformed salt in token envelope")?;
+    let nonce_bytes =
+        base64_decode(&envelope.nonce).context("malformed nonce in token envelope")?;
+    let ciphertext =
+        base64_decode(&envelope.ciphertext).context("malformed ciphertext in token envelope")?;
+
+    if salt.len() != SALT_LEN || nonce_bytes.len() != NONCE_LEN {
+        bail!("corrupt token envelope: unexpected salt/nonce length");
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext.as_slice())
+        .map_err(|_| anyhow::anyhow!("wrong passphrase or tampered token file"))
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+fn base64_decode(s: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(s)
+        .context("invalid base64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_seal_open() {
+        let envelope = seal("correct horse", b"top secret tokens").unwrap();
+        let plaintext = open("correct horse", &envelope).unwrap();
+        assert_eq!(plaintext, b"top secret tokens");
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let envelope = seal("correct horse", b"top secret tokens").unwrap();
+        assert!(open("wrong passphrase", &envelope).is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails() {
+        let mut envelope = seal("correct horse", b"top secret tokens").unwrap();
+        envelope.ciphertext = seal("correct horse", b"different bytes!").unwrap().ciphertext;
+        assert!(open("correct horse", &envelope).is_err());
+    }
+}
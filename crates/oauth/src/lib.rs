@@ -1,10 +1,14 @@
 pub mod callback_server;
+pub mod crypto;
 pub mod flow;
 pub mod pkce;
+pub mod providers;
+pub mod refresh;
 pub mod storage;
 pub mod types;
 
 pub use callback_server::CallbackServer;
 pub use flow::OAuthFlow;
+pub use providers::ProviderRegistry;
 pub use storage::TokenStore;
 pub use types::{OAuthConfig, OAuthTokens, PkceChallenge};
@@ -0,0 +1,117 @@
+//! Config-driven OAuth provider registry.
+//!
+//! Provider configs (client_id/auth_url/token_url/scopes) used to be a hard-coded
+//! `match` in the CLI. They now load from `~/.moltis/oauth-providers.yaml`, which
+//! overrides or adds to the built-in defaults below — so `moltis auth login` keeps
+//! working out of the box for known providers while letting users add their own.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::types::OAuthConfig;
+
+const DEFAULT_REDIRECT_URI: &str = "http://127.0.0.1:1455/auth/callback";
+
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderFileConfig {
+    client_id: String,
+    auth_url: String,
+    token_url: String,
+    #[serde(default)]
+    redirect_uri: Option<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ProvidersFile {
+    #[serde(default)]
+    providers: HashMap<String, ProviderFileConfig>,
+}
+
+/// Looks up OAuth provider configuration by name, merging built-in defaults with
+/// `~/.moltis/oauth-providers.yaml` overrides.
+pub struct ProviderRegistry {
+    providers: HashMap<String, OAuthConfig>,
+}
+
+impl ProviderRegistry {
+    /// Load the registry: built-in defaults, overridden/extended by
+    /// `~/.moltis/oauth-providers.yaml` if present.
+    pub fn load() -> Result<Self> {
+        let mut providers = builtin_defaults();
+
+        let path = config_path();
+        if path.is_file() {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let file: ProvidersFile = serde_yaml::from_str(&contents)
+                .with_context(|| format!("invalid OAuth provider config at {}", path.display()))?;
+
+            for (name, cfg) in file.providers {
+                providers.insert(
+                    name,
+                    OAuthConfig {
+                        client_id: cfg.client_id,
+                        auth_url: cfg.auth_url,
+                        token_url: cfg.token_url,
+                        redirect_uri: cfg
+                            .redirect_uri
+                            .unwrap_or_else(|| DEFAULT_REDIRECT_URI.to_string()),
+                        scopes: cfg.scopes,
+                    },
+                );
+            }
+        }
+
+        Ok(Self { providers })
+    }
+
+    /// Look up a provider's config by name.
+    pub fn get(&self, provider: &str) -> Result<OAuthConfig> {
+        self.providers
+            .get(provider)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("unknown provider: {provider}"))
+    }
+
+    /// All known provider names, for `moltis auth` diagnostics.
+    pub fn provider_names(&self) -> Vec<String> {
+        self.providers.keys().cloned().collect()
+    }
+}
+
+fn config_path() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.home_dir().join(".moltis/oauth-providers.yaml"))
+        .unwrap_or_else(|| PathBuf::from(".moltis/oauth-providers.yaml"))
+}
+
+fn builtin_defaults() -> HashMap<String, OAuthConfig> {
+    let mut map = HashMap::new();
+    map.insert(
+        "openai-codex".to_string(),
+        OAuthConfig {
+            client_id: "pdlLIX2Y72MIl2rhLhTE9VV9bN905kBh".to_string(),
+            auth_url: "https://auth.openai.com/oauth/authorize".to_string(),
+            token_url: "https://auth.openai.com/oauth/token".to_string(),
+            redirect_uri: DEFAULT_REDIRECT_URI.to_string(),
+            scopes: vec![],
+        },
+    );
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_openai_codex_is_available_with_no_config_file() {
+        let providers = builtin_defaults();
+        assert!(providers.contains_key("openai-codex"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Background OAuth token refresh.
+//!
+//! Long-running daemons shouldn't ever hand a provider an expired access token. This
+//! periodically scans the [`TokenStore`] and, for any provider whose `expires_at` is
+//! within [`REFRESH_THRESHOLD_SECS`] of now, performs the OAuth2 refresh-token grant
+//! against its `token_url` and re-persists the result.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::providers::ProviderRegistry;
+use crate::storage::TokenStore;
+use crate::types::OAuthTokens;
+
+/// Refresh tokens that expire within this many seconds of "now".
+const REFRESH_THRESHOLD_SECS: u64 = 5 * 60;
+/// How often to scan the token store for tokens needing refresh.
+const SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// Spawn a background task that refreshes stored provider tokens as they approach
+/// expiry. Runs for the process lifetime; a failure for one provider is logged and
+/// doesn't block the others.
+pub fn spawn_refresh_task(store: Arc<TokenStore>, registry: Arc<ProviderRegistry>) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(SCAN_INTERVAL).await;
+            refresh_due_tokens(&store, &registry).await;
+        }
+    });
+}
+
+async fn refresh_due_tokens(store: &TokenStore, registry: &ProviderRegistry) {
+    for provider in store.list() {
+        let tokens = match store.load(&provider) {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!(provider, error = %e, "failed to load stored token, skipping refresh");
+                continue;
+            },
+        };
+        if !needs_refresh(&tokens) {
+            continue;
+        }
+        let Some(refresh_token) = tokens.refresh_token.clone() else {
+            debug!(provider, "token needs refresh but no refresh_token stored, skipping");
+            continue;
+        };
+        let config = match registry.get(&provider) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!(provider, error = %e, "no provider config for stored token, skipping refresh");
+                continue;
+            },
+        };
+
+        match refresh_token_grant(&config.token_url, &config.client_id, &refresh_token).await {
+            Ok(new_tokens) => match store.save(&provider, &new_tokens) {
+                Ok(()) => info!(provider, "refreshed OAuth token"),
+                Err(e) => warn!(provider, error = %e, "failed to persist refreshed token"),
+            },
+            Err(e) => warn!(provider, error = %e, "failed to refresh OAuth token"),
+        }
+    }
+}
+
+fn needs_refresh(tokens: &OAuthTokens) -> bool {
+    let Some(expires_at) = tokens.expires_at else {
+        return false;
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    expires_at <= now + REFRESH_THRESHOLD_SECS
+}
+
+async fn refresh_token_grant(
+    token_url: &str,
+    client_id: &str,
+    refresh_token: &str,
+) -> anyhow::Result<OAuthTokens> {
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(token_url)
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", client_id),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<RefreshResponse>()
+        .await?;
+
+    let expires_at = resp.expires_in.map(|secs| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            + secs
+    });
+
+    Ok(OAuthTokens {
+        access_token: resp.access_token,
+        refresh_token: resp.refresh_token.or(Some(refresh_token.to_string())),
+        expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_without_expiry_never_needs_refresh() {
+        let tokens = OAuthTokens {
+            access_token: "a".into(),
+            refresh_token: Some("r".into()),
+            expires_at: None,
+        };
+        assert!(!needs_refresh(&tokens));
+    }
+
+    #[test]
+    fn token_expiring_soon_needs_refresh() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let tokens = OAuthTokens {
+            access_token: "a".into(),
+            refresh_token: Some("r".into()),
+            expires_at: Some(now + 30),
+        };
+        assert!(needs_refresh(&tokens));
+    }
+
+    #[test]
+    fn token_far_from_expiry_does_not_need_refresh() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let tokens = OAuthTokens {
+            access_token: "a".into(),
+            refresh_token: Some("r".into()),
+            expires_at: Some(now + 3600),
+        };
+        assert!(!needs_refresh(&tokens));
+    }
+}
@@ -0,0 +1,217 @@
+//! Persists [`OAuthTokens`] to `~/.moltis/auth/<provider>.json`, one file per provider.
+//!
+//! Supports a plaintext mode (the historical default) and an encrypted-at-rest mode
+//! where each file holds a [`crypto::SealedEnvelope`] instead of raw JSON. The mode is
+//! fixed per [`TokenStore`] instance so callers opt in explicitly via [`TokenStore::encrypted`].
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::crypto::{self, SealedEnvelope};
+use crate::types::OAuthTokens;
+
+/// Environment variable consulted before falling back to the OS keyring.
+const PASSPHRASE_ENV: &str = "MOLTIS_TOKEN_PASSPHRASE";
+const KEYRING_SERVICE: &str = "moltis";
+const KEYRING_USER: &str = "token-store";
+
+enum StoreMode {
+    Plaintext,
+    Encrypted { passphrase: String },
+}
+
+/// File-backed store for OAuth tokens, one JSON (or sealed) file per provider.
+pub struct TokenStore {
+    base_dir: PathBuf,
+    mode: StoreMode,
+}
+
+impl TokenStore {
+    /// Plaintext store (backward-compatible default).
+    pub fn new() -> Self {
+        Self {
+            base_dir: default_base_dir(),
+            mode: StoreMode::Plaintext,
+        }
+    }
+
+    /// Encrypted-at-rest store. Resolves the passphrase from `MOLTIS_TOKEN_PASSPHRASE`,
+    /// falling back to the OS keyring entry (`moltis`/`token-store`).
+    pub fn encrypted() -> Result<Self> {
+        let passphrase = resolve_passphrase()?;
+        Ok(Self {
+            base_dir: default_base_dir(),
+            mode: StoreMode::Encrypted { passphrase },
+        })
+    }
+
+    fn path_for(&self, provider: &str) -> PathBuf {
+        self.base_dir.join(format!("{provider}.json"))
+    }
+
+    /// Persist tokens for `provider`, overwriting any existing entry.
+    pub fn save(&self, provider: &str, tokens: &OAuthTokens) -> Result<()> {
+        std::fs::create_dir_all(&self.base_dir).context("failed to create token store dir")?;
+        let path = self.path_for(provider);
+
+        let serialized = match &self.mode {
+            StoreMode::Plaintext => serde_json::to_string_pretty(tokens)?,
+            StoreMode::Encrypted { passphrase } => {
+                let plaintext = serde_json::to_vec(tokens)?;
+                let envelope = crypto::seal(passphrase, &plaintext)?;
+                serde_json::to_string_pretty(&envelope)?
+            },
+        };
+
+        std::fs::write(&path, serialized)
+            .with_context(|| format!("failed to write token file at {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Load tokens for `provider`, if present. `Ok(None)` means no token file exists
+    /// yet (not logged in). A wrong passphrase or tampered/corrupt file is a distinct
+    /// `Err` rather than being folded into `Ok(None)`, so callers can tell a user
+    /// "your passphrase is wrong" instead of "no authenticated providers".
+    pub fn load(&self, provider: &str) -> Result<Option<OAuthTokens>> {
+        let path = self.path_for(provider);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read token file at {}", path.display()))
+            },
+        };
+
+        match &self.mode {
+            StoreMode::Plaintext => Ok(Some(
+                serde_json::from_str(&contents).context("failed to parse token file")?,
+            )),
+            StoreMode::Encrypted { passphrase } => {
+                let envelope: SealedEnvelope =
+                    serde_json::from_str(&contents).context("failed to parse sealed token envelope")?;
+                let plaintext = crypto::open(passphrase, &envelope)?;
+                Ok(Some(serde_json::from_slice(&plaintext).context("failed to parse decrypted token data")?))
+            },
+        }
+    }
+
+    /// List all providers with a stored token file.
+    pub fn list(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.base_dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|e| {
+                let path = e.path();
+                if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                    path.file_stem()?.to_str().map(str::to_string)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Remove the stored tokens for `provider`.
+    pub fn delete(&self, provider: &str) -> Result<()> {
+        let path = self.path_for(provider);
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("failed to remove token file at {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for TokenStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn default_base_dir() -> PathBuf {
+    directories::BaseDirs::new()
+        .map(|d| d.home_dir().join(".moltis/auth"))
+        .unwrap_or_else(|| PathBuf::from(".moltis/auth"))
+}
+
+fn resolve_passphrase() -> Result<String> {
+    if let Ok(p) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(p);
+    }
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .context("failed to open OS keyring entry")?;
+    entry.get_password().context(
+        "no token store passphrase found: set MOLTIS_TOKEN_PASSPHRASE or store one in the OS keyring",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(mode: StoreMode) -> TokenStore {
+        let dir = tempfile::tempdir().unwrap().into_path();
+        TokenStore {
+            base_dir: dir,
+            mode,
+        }
+    }
+
+    fn sample_tokens() -> OAuthTokens {
+        OAuthTokens {
+            access_token: "access".into(),
+            refresh_token: Some("refresh".into()),
+            expires_at: Some(1_000_000),
+        }
+    }
+
+    #[test]
+    fn plaintext_roundtrip() {
+        let store = temp_store(StoreMode::Plaintext);
+        store.save("openai-codex", &sample_tokens()).unwrap();
+        let loaded = store.load("openai-codex").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+        assert_eq!(store.list(), vec!["openai-codex".to_string()]);
+        store.delete("openai-codex").unwrap();
+        assert!(store.load("openai-codex").unwrap().is_none());
+    }
+
+    #[test]
+    fn encrypted_roundtrip() {
+        let store = temp_store(StoreMode::Encrypted {
+            passphrase: "hunter2".into(),
+        });
+        store.save("openai-codex", &sample_tokens()).unwrap();
+        let loaded = store.load("openai-codex").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+
+        // Raw file on disk must not contain the plaintext access token.
+        let raw = std::fs::read_to_string(store.path_for("openai-codex")).unwrap();
+        assert!(!raw.contains("access"));
+    }
+
+    #[test]
+    fn encrypted_wrong_passphrase_is_an_error_not_not_logged_in() {
+        let store = temp_store(StoreMode::Encrypted {
+            passphrase: "hunter2".into(),
+        });
+        store.save("openai-codex", &sample_tokens()).unwrap();
+
+        let wrong = TokenStore {
+            base_dir: store.base_dir.clone(),
+            mode: StoreMode::Encrypted {
+                passphrase: "wrong".into(),
+            },
+        };
+        assert!(wrong.load("openai-codex").is_err());
+    }
+
+    #[test]
+    fn missing_file_returns_ok_none() {
+        let store = temp_store(StoreMode::Plaintext);
+        assert!(store.load("no-such-provider").unwrap().is_none());
+    }
+}
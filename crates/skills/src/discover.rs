@@ -41,6 +41,7 @@ impl FsSkillDiscoverer {
 #[async_trait]
 impl SkillDiscoverer for FsSkillDiscoverer {
     async fn discover(&self) -> anyhow::Result<Vec<SkillMetadata>> {
+        let start = std::time::Instant::now();
         let mut skills = Vec::new();
 
         for (base_path, source) in &self.search_paths {
@@ -80,6 +81,12 @@ impl SkillDiscoverer for FsSkillDiscoverer {
             }
         }
 
+        metrics::histogram!("skill_discovery_duration_seconds").record(start.elapsed().as_secs_f64());
+        for (_, source) in &self.search_paths {
+            let count = skills.iter().filter(|s| s.source.as_ref() == Some(source)).count();
+            metrics::gauge!("skills_discovered", "source" => format!("{source:?}")).set(count as f64);
+        }
+
         Ok(skills)
     }
 }
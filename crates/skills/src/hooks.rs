@@ -0,0 +1,120 @@
+//! Runs a skill's declared hook commands around chat sends.
+//!
+//! Each hook point is a shell command invoked with a JSON payload on stdin and expected
+//! to print a JSON response on stdout. `before_send` hooks may rewrite or veto the
+//! outgoing messages; `after_send`/`on_error` hooks are fire-and-forget notifications
+//! whose failures are isolated so one broken skill can't abort a run.
+
+use std::process::Stdio;
+
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Result of running a `before_send` hook.
+pub enum BeforeSendOutcome {
+    /// Proceed with (possibly rewritten) messages.
+    Continue { messages: Vec<serde_json::Value> },
+    /// Veto the send; `reason` is broadcast to the caller as a `state: "blocked"` event.
+    Block { reason: String },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum BeforeSendResponse {
+    Continue {
+        #[serde(default)]
+        messages: Option<Vec<serde_json::Value>>,
+    },
+    Block {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+/// Run a `before_send` hook. On any failure (spawn error, bad JSON, non-zero exit) the
+/// hook is treated as a no-op `Continue` — a broken hook isolates itself rather than
+/// aborting the send, per the skill's veto being an explicit `Block` response.
+pub async fn run_before_send(
+    command: &str,
+    messages: &[serde_json::Value],
+) -> BeforeSendOutcome {
+    let passthrough = || BeforeSendOutcome::Continue {
+        messages: messages.to_vec(),
+    };
+
+    let payload = serde_json::json!({ "hook": "before_send", "messages": messages });
+
+    match run_hook(command, &payload).await {
+        Ok(stdout) => match serde_json::from_str::<BeforeSendResponse>(&stdout) {
+            Ok(BeforeSendResponse::Block { reason }) => BeforeSendOutcome::Block {
+                reason: reason.unwrap_or_else(|| "blocked by skill hook".to_string()),
+            },
+            Ok(BeforeSendResponse::Continue { messages: rewritten }) => {
+                BeforeSendOutcome::Continue {
+                    messages: rewritten.unwrap_or_else(|| messages.to_vec()),
+                }
+            },
+            Err(e) => {
+                tracing::warn!(command, error = %e, "before_send hook returned invalid JSON, ignoring");
+                passthrough()
+            },
+        },
+        Err(e) => {
+            tracing::warn!(command, error = %e, "before_send hook failed, ignoring");
+            passthrough()
+        },
+    }
+}
+
+/// Run an `after_send` hook with the accumulated text and token usage. Failures are
+/// logged and swallowed; this is a notification, not a gate.
+pub async fn run_after_send(command: &str, text: &str, input_tokens: u32, output_tokens: u32) {
+    let payload = serde_json::json!({
+        "hook": "after_send",
+        "text": text,
+        "usage": { "inputTokens": input_tokens, "outputTokens": output_tokens },
+    });
+    if let Err(e) = run_hook(command, &payload).await {
+        tracing::warn!(command, error = %e, "after_send hook failed");
+    }
+}
+
+/// Run an `on_error` hook with the error message. Failures are logged and swallowed.
+pub async fn run_on_error(command: &str, error: &str) {
+    let payload = serde_json::json!({ "hook": "on_error", "error": error });
+    if let Err(e) = run_hook(command, &payload).await {
+        tracing::warn!(command, error = %e, "on_error hook failed");
+    }
+}
+
+/// Spawn `command` via the shell, write `payload` to its stdin as JSON, and return its
+/// trimmed stdout. Errors if the process fails to spawn, exits non-zero, or times out.
+async fn run_hook(command: &str, payload: &serde_json::Value) -> anyhow::Result<String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(serde_json::to_vec(payload)?.as_slice()).await?;
+    }
+
+    let timeout = tokio::time::Duration::from_secs(10);
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| anyhow::anyhow!("hook command timed out after {}s", timeout.as_secs()))??;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "hook command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,13 +1,34 @@
 use std::path::{Path, PathBuf};
 
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
 use crate::{parse, types::SkillMetadata};
 
+/// Sidecar file written next to every installed skill, recording where it came from
+/// and a checksum of its contents at install time. `update_skill` reads this back to
+/// know what to re-fetch.
+const INSTALL_RECORD_FILE: &str = ".moltis-skill.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InstallRecord {
+    /// The original `owner/repo` or `owner/repo@ref` source string.
+    source: String,
+    /// The commit/tag actually resolved at install time, if known.
+    resolved_ref: Option<String>,
+    /// SHA-256 over the installed SKILL.md and asset files, for drift detection.
+    checksum: String,
+}
+
 /// Install a skill from a GitHub repository into the target directory.
 ///
-/// Tries `git clone --depth=1` first, falls back to HTTP tarball fetch.
-/// The `source` should be `owner/repo` format (e.g. `vercel-labs/agent-skills`).
+/// Tries `git clone --depth=1` first, falls back to HTTP tarball fetch. `source` is
+/// `owner/repo` (e.g. `vercel-labs/agent-skills`), optionally pinned to a tag/commit
+/// with `owner/repo@ref`. A content-digest pin (see [`parse_source`]) uses the HTTP
+/// tarball fetch only, since that is where the digest is checked.
 pub async fn install_skill(source: &str, install_dir: &Path) -> anyhow::Result<SkillMetadata> {
-    let (owner, repo) = parse_source(source)?;
+    let (owner, repo, pinned_ref, expected_digest) = parse_source(source)?;
     let target = install_dir.join(&repo);
 
     if target.exists() {
@@ -19,30 +40,157 @@ pub async fn install_skill(source: &str, install_dir: &Path) -> anyhow::Result<S
 
     tokio::fs::create_dir_all(install_dir).await?;
 
-    // Try git clone first
-    let git_url = format!("https://github.com/{owner}/{repo}");
-    let git_result = tokio::process::Command::new("git")
-        .args(["clone", "--depth=1", &git_url, &target.to_string_lossy()])
-        .output()
-        .await;
+    // A content digest can only be checked against the tarball bytes, so a digest pin
+    // skips git clone.
+    let resolved_ref = if expected_digest.is_some() {
+        install_via_http(&owner, &repo, pinned_ref.as_deref(), expected_digest.as_deref(), &target).await?
+    } else {
+        match clone_via_git(&owner, &repo, pinned_ref.as_deref(), &target).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                tracing::warn!(%source, error = %e, "git clone failed, falling back to HTTP tarball");
+                install_via_http(&owner, &repo, pinned_ref.as_deref(), None, &target).await?
+            },
+        }
+    };
+
+    activate_installed_skill(&target, source, resolved_ref).await
+}
 
-    match git_result {
-        Ok(output) if output.status.success() => {
-            tracing::info!(%source, "installed skill via git clone");
+/// Re-fetch an installed skill from its recorded source, replacing the existing
+/// install directory only once the refetch is confirmed good. Clones/extracts into a
+/// staging directory, validates it via [`install_skill`] (SKILL.md present, checksum
+/// computed), and only then swaps it in for the live directory — so a failed refetch
+/// (network error, missing SKILL.md, checksum mismatch) leaves the working install
+/// untouched instead of deleting it up front. Logs whether the upstream content
+/// actually changed.
+pub async fn update_skill(name: &str, install_dir: &Path) -> anyhow::Result<SkillMetadata> {
+    let target = install_dir.join(name);
+    let record = read_install_record(&target)
+        .await
+        .with_context(|| format!("'{name}' has no install record; was it installed via `skills add`?"))?;
+
+    let staging_dir = install_dir.join(format!(".{name}.update-{}", std::process::id()));
+    if staging_dir.exists() {
+        let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+    }
+    tokio::fs::create_dir_all(&staging_dir).await?;
+
+    let meta = match install_skill(&record.source, &staging_dir).await {
+        Ok(meta) => meta,
+        Err(e) => {
+            let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+            return Err(e);
         },
-        _ => {
-            // Fallback: HTTP fetch of the default branch tarball
-            return install_via_http(&owner, &repo, &target).await;
+    };
+
+    let staged_target = match find_only_subdir(&staging_dir).await {
+        Ok(Some(path)) => path,
+        Ok(None) | Err(_) => {
+            let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+            anyhow::bail!("staged install for '{name}' produced no install directory");
+        },
+    };
+    let new_record = match read_install_record(&staged_target).await {
+        Ok(record) => record,
+        Err(e) => {
+            let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+            return Err(e);
         },
+    };
+
+    // The staged install is confirmed good; swap it in for the live directory.
+    tokio::fs::remove_dir_all(&target)
+        .await
+        .with_context(|| format!("failed to remove existing install at {}", target.display()))?;
+    tokio::fs::rename(&staged_target, &target)
+        .await
+        .with_context(|| format!("failed to move staged install into place at {}", target.display()))?;
+    let _ = tokio::fs::remove_dir_all(&staging_dir).await;
+
+    if new_record.checksum == record.checksum {
+        tracing::info!(name, "skill already up to date");
+    } else {
+        tracing::info!(name, "updated skill to new upstream content");
+    }
+
+    Ok(meta)
+}
+
+/// Return the single subdirectory of `dir`, if it contains exactly one. Used to find
+/// the install produced by [`install_skill`] inside a staging directory without
+/// needing to re-derive the repo name from the source string.
+async fn find_only_subdir(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    let mut found = None;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() {
+            if found.is_some() {
+                return Ok(None);
+            }
+            found = Some(entry.path());
+        }
+    }
+    Ok(found)
+}
+
+async fn read_install_record(skill_dir: &Path) -> anyhow::Result<InstallRecord> {
+    let bytes = tokio::fs::read(skill_dir.join(INSTALL_RECORD_FILE)).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Clone `owner/repo` via git, checking out `pinned_ref` if given, and return the
+/// commit SHA actually checked out.
+async fn clone_via_git(
+    owner: &str,
+    repo: &str,
+    pinned_ref: Option<&str>,
+    target: &Path,
+) -> anyhow::Result<Option<String>> {
+    let git_url = format!("https://github.com/{owner}/{repo}");
+    let target_str = target.to_string_lossy().to_string();
+
+    let mut args = vec!["clone".to_string(), "--depth=1".to_string()];
+    if let Some(r) = pinned_ref {
+        args.push("--branch".to_string());
+        args.push(r.to_string());
+    }
+    args.push(git_url);
+    args.push(target_str.clone());
+
+    let output = tokio::process::Command::new("git").args(&args).output().await?;
+    if !output.status.success() {
+        anyhow::bail!("git clone failed: {}", String::from_utf8_lossy(&output.stderr).trim());
     }
 
-    // Validate the installed skill
-    validate_installed_skill(&target).await
+    let rev_output = tokio::process::Command::new("git")
+        .args(["-C", &target_str, "rev-parse", "HEAD"])
+        .output()
+        .await?;
+    let resolved = rev_output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&rev_output.stdout).trim().to_string());
+
+    tracing::info!(owner, repo, ?pinned_ref, "installed skill via git clone");
+    Ok(resolved)
 }
 
-/// Install by fetching a tarball from GitHub's API.
-async fn install_via_http(owner: &str, repo: &str, target: &Path) -> anyhow::Result<SkillMetadata> {
-    let url = format!("https://api.github.com/repos/{owner}/{repo}/tarball");
+/// Install by fetching a tarball from GitHub's API, optionally pinned to `pinned_ref`.
+/// If `expected_digest` (a hex-encoded SHA-256) is given, the downloaded bytes are
+/// hashed and verified against it before extraction, so a tampered or corrupted
+/// download is caught instead of silently unpacked.
+async fn install_via_http(
+    owner: &str,
+    repo: &str,
+    pinned_ref: Option<&str>,
+    expected_digest: Option<&str>,
+    target: &Path,
+) -> anyhow::Result<Option<String>> {
+    let url = match pinned_ref {
+        Some(r) => format!("https://api.github.com/repos/{owner}/{repo}/tarball/{r}"),
+        None => format!("https://api.github.com/repos/{owner}/{repo}/tarball"),
+    };
     let client = reqwest::Client::new();
     let resp = client
         .get(&url)
@@ -56,6 +204,13 @@ async fn install_via_http(owner: &str, repo: &str, target: &Path) -> anyhow::Res
 
     let bytes = resp.bytes().await?;
 
+    if let Some(expected) = expected_digest {
+        let actual = format!("{:x}", Sha256::digest(&bytes));
+        if !actual.eq_ignore_ascii_case(expected) {
+            anyhow::bail!("checksum mismatch for {owner}/{repo}: expected {expected}, got {actual}");
+        }
+    }
+
     // Extract tarball to target
     tokio::fs::create_dir_all(target).await?;
     let target_owned = target.to_path_buf();
@@ -82,14 +237,19 @@ async fn install_via_http(owner: &str, repo: &str, target: &Path) -> anyhow::Res
     .await??;
 
     tracing::info!(%owner, %repo, "installed skill via HTTP tarball");
-    validate_installed_skill(target).await
+    Ok(pinned_ref.map(str::to_string))
 }
 
-/// Validate that a SKILL.md exists and parses correctly in the installed directory.
-async fn validate_installed_skill(skill_dir: &Path) -> anyhow::Result<SkillMetadata> {
+/// Validate that a SKILL.md exists and parses correctly, compute its integrity
+/// checksum, and write the install record — all before the skill is considered
+/// "activated" and discoverable via [`crate::types::SkillSource::Registry`].
+async fn activate_installed_skill(
+    skill_dir: &Path,
+    source: &str,
+    resolved_ref: Option<String>,
+) -> anyhow::Result<SkillMetadata> {
     let skill_md = skill_dir.join("SKILL.md");
     if !skill_md.exists() {
-        // Clean up
         let _ = tokio::fs::remove_dir_all(skill_dir).await;
         anyhow::bail!(
             "installed repository does not contain a SKILL.md at {}",
@@ -98,28 +258,98 @@ async fn validate_installed_skill(skill_dir: &Path) -> anyhow::Result<SkillMetad
     }
 
     let content = tokio::fs::read_to_string(&skill_md).await?;
-    match parse::parse_metadata(&content, skill_dir) {
-        Ok(mut meta) => {
-            meta.source = Some(crate::types::SkillSource::Registry);
-            Ok(meta)
+    let mut meta = match parse::parse_metadata(&content, skill_dir) {
+        Ok(meta) => meta,
+        Err(e) => {
+            let _ = tokio::fs::remove_dir_all(skill_dir).await;
+            return Err(e);
         },
+    };
+    meta.source = Some(crate::types::SkillSource::Registry);
+    meta.installed_ref = resolved_ref.clone();
+
+    let checksum = match compute_skill_checksum(skill_dir) {
+        Ok(checksum) => checksum,
         Err(e) => {
             let _ = tokio::fs::remove_dir_all(skill_dir).await;
-            Err(e)
+            anyhow::bail!("failed to verify installed skill integrity: {e}");
         },
+    };
+
+    let record = InstallRecord {
+        source: source.to_string(),
+        resolved_ref,
+        checksum,
+    };
+    let record_path = skill_dir.join(INSTALL_RECORD_FILE);
+    if let Err(e) = tokio::fs::write(&record_path, serde_json::to_vec_pretty(&record)?).await {
+        tracing::warn!(error = %e, "failed to write skill install record");
+    }
+
+    Ok(meta)
+}
+
+/// Hash every file under `skill_dir` (path + contents, sorted for determinism) for the
+/// install record's drift-detection checksum. The result is recorded, not compared
+/// against an expected value; download verification is the tarball digest check in
+/// [`install_via_http`].
+fn compute_skill_checksum(skill_dir: &Path) -> anyhow::Result<String> {
+    let mut paths = Vec::new();
+    collect_files(skill_dir, skill_dir, &mut paths)?;
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    for rel in &paths {
+        if rel == INSTALL_RECORD_FILE {
+            continue;
+        }
+        hasher.update(rel.as_bytes());
+        hasher.update(std::fs::read(skill_dir.join(rel))?);
     }
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
-/// Parse `owner/repo` from a source string.
-fn parse_source(source: &str) -> anyhow::Result<(String, String)> {
-    let parts: Vec<&str> = source.trim().split('/').collect();
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().and_then(|n| n.to_str()) == Some(".git") {
+                continue;
+            }
+            collect_files(root, &path, out)?;
+        } else {
+            out.push(path.strip_prefix(root)?.to_string_lossy().into_owned());
+        }
+    }
+    Ok(())
+}
+
+/// Parse `owner/repo` or `owner/repo@ref` from a source string. `ref` may be a tag,
+/// branch, or commit SHA passed straight through to `git clone --branch`/the tarball
+/// URL; a bare 64-character hex string is instead treated as an expected SHA-256
+/// content digest (verified against the downloaded tarball, not used as a git ref),
+/// letting an install use whatever upstream's default branch/commit is while still
+/// pinning its content.
+fn parse_source(source: &str) -> anyhow::Result<(String, String, Option<String>, Option<String>)> {
+    let (repo_part, pin) = match source.trim().split_once('@') {
+        Some((repo_part, r)) => (repo_part, Some(r.to_string())),
+        None => (source.trim(), None),
+    };
+
+    let parts: Vec<&str> = repo_part.split('/').collect();
     if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
         anyhow::bail!(
-            "invalid skill source '{}': expected 'owner/repo' format",
+            "invalid skill source '{}': expected 'owner/repo' or 'owner/repo@ref' format",
             source
         );
     }
-    Ok((parts[0].to_string(), parts[1].to_string()))
+
+    let (pinned_ref, expected_digest) = match pin {
+        Some(p) if p.len() == 64 && p.chars().all(|c| c.is_ascii_hexdigit()) => (None, Some(p)),
+        other => (other, None),
+    };
+
+    Ok((parts[0].to_string(), parts[1].to_string(), pinned_ref, expected_digest))
 }
 
 /// Get the default installation directory.
@@ -135,9 +365,29 @@ mod tests {
 
     #[test]
     fn test_parse_source_valid() {
-        let (owner, repo) = parse_source("vercel-labs/agent-skills").unwrap();
+        let (owner, repo, pinned_ref, digest) = parse_source("vercel-labs/agent-skills").unwrap();
         assert_eq!(owner, "vercel-labs");
         assert_eq!(repo, "agent-skills");
+        assert_eq!(pinned_ref, None);
+        assert_eq!(digest, None);
+    }
+
+    #[test]
+    fn test_parse_source_with_pinned_ref() {
+        let (owner, repo, pinned_ref, digest) = parse_source("vercel-labs/agent-skills@v1.2.3").unwrap();
+        assert_eq!(owner, "vercel-labs");
+        assert_eq!(repo, "agent-skills");
+        assert_eq!(pinned_ref, Some("v1.2.3".to_string()));
+        assert_eq!(digest, None);
+    }
+
+    #[test]
+    fn test_parse_source_with_expected_digest() {
+        let sha256 = "a".repeat(64);
+        let (_, _, pinned_ref, digest) =
+            parse_source(&format!("vercel-labs/agent-skills@{sha256}")).unwrap();
+        assert_eq!(pinned_ref, None);
+        assert_eq!(digest, Some(sha256));
     }
 
     #[test]
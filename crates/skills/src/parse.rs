@@ -2,7 +2,7 @@ use std::path::Path;
 
 use anyhow::{Context, bail};
 
-use crate::types::{SkillContent, SkillMetadata};
+use crate::types::{SkillContent, SkillMetadata, ToolSpec};
 
 /// Validate a skill name: lowercase ASCII, hyphens, 1-64 chars.
 pub fn validate_name(name: &str) -> bool {
@@ -27,6 +27,7 @@ pub fn parse_metadata(content: &str, skill_dir: &Path) -> anyhow::Result<SkillMe
             meta.name
         );
     }
+    validate_tool_specs(&meta.allowed_tools)?;
 
     meta.path = skill_dir.to_path_buf();
     Ok(meta)
@@ -44,6 +45,7 @@ pub fn parse_skill(content: &str, skill_dir: &Path) -> anyhow::Result<SkillConte
             meta.name
         );
     }
+    validate_tool_specs(&meta.allowed_tools)?;
 
     meta.path = skill_dir.to_path_buf();
     Ok(SkillContent {
@@ -52,6 +54,23 @@ pub fn parse_skill(content: &str, skill_dir: &Path) -> anyhow::Result<SkillConte
     })
 }
 
+/// Reject tool entries whose `parameters` is present but isn't a JSON object, so a
+/// misconfigured skill fails loudly here rather than producing a malformed `tools`
+/// payload when it's later sent to a provider.
+fn validate_tool_specs(tools: &[ToolSpec]) -> anyhow::Result<()> {
+    for tool in tools {
+        if let Some(params) = tool.parameters()
+            && !params.is_object()
+        {
+            bail!(
+                "tool '{}' has a non-object `parameters` schema: JSON Schema objects expected",
+                tool.name()
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Split SKILL.md content at `---` delimiters into (frontmatter, body).
 fn split_frontmatter(content: &str) -> anyhow::Result<(String, String)> {
     let trimmed = content.trim_start();
@@ -106,10 +125,53 @@ Instructions here.
         assert_eq!(meta.name, "my-skill");
         assert_eq!(meta.description, "A test skill");
         assert_eq!(meta.license, Some("MIT".into()));
-        assert_eq!(meta.allowed_tools, vec!["exec", "read"]);
+        assert_eq!(
+            meta.allowed_tools.iter().map(ToolSpec::name).collect::<Vec<_>>(),
+            vec!["exec", "read"]
+        );
         assert_eq!(meta.path, Path::new("/tmp/my-skill"));
     }
 
+    #[test]
+    fn test_parse_metadata_full_tool_spec() {
+        let content = r#"---
+name: my-skill
+allowed_tools:
+  - exec
+  - name: read_file
+    description: Read a file's contents
+    parameters:
+      type: object
+      properties:
+        path:
+          type: string
+      required: [path]
+---
+
+Instructions here.
+"#;
+        let meta = parse_metadata(content, Path::new("/tmp/my-skill")).unwrap();
+        assert_eq!(meta.allowed_tools[0], ToolSpec::Name("exec".into()));
+        assert_eq!(meta.allowed_tools[1].name(), "read_file");
+        assert_eq!(meta.allowed_tools[1].description(), "Read a file's contents");
+        assert!(meta.allowed_tools[1].parameters().unwrap().is_object());
+    }
+
+    #[test]
+    fn test_parse_metadata_rejects_non_object_parameters() {
+        let content = r#"---
+name: my-skill
+allowed_tools:
+  - name: read_file
+    parameters: "not an object"
+---
+
+body
+"#;
+        let err = parse_metadata(content, Path::new("/tmp/my-skill")).unwrap_err();
+        assert!(err.to_string().contains("non-object"));
+    }
+
     #[test]
     fn test_parse_skill_full() {
         let content = r#"---
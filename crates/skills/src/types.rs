@@ -94,15 +94,92 @@ pub struct SkillMetadata {
     /// SPDX license identifier.
     #[serde(default)]
     pub license: Option<String>,
-    /// Tools this skill is allowed to use.
+    /// Tools this skill is allowed to use, each either a bare name or a full
+    /// definition carrying a JSON Schema `parameters` object for tool-calling.
     #[serde(default)]
-    pub allowed_tools: Vec<String>,
+    pub allowed_tools: Vec<ToolSpec>,
+    /// Hook commands this skill registers. They run around chat sends only; agent
+    /// invocations do not run them.
+    #[serde(default)]
+    pub hooks: SkillHooks,
     /// Filesystem path to the skill directory.
     #[serde(skip)]
     pub path: PathBuf,
     /// Where this skill was discovered.
     #[serde(skip)]
     pub source: Option<SkillSource>,
+    /// For skills installed via `install_skill`, the exact ref/commit that was
+    /// resolved at install time (see `moltis_skills::install::InstallRecord`).
+    #[serde(skip)]
+    pub installed_ref: Option<String>,
+}
+
+/// One tool a skill is allowed to use. Accepts the legacy flat `- name` form, or a
+/// full object form carrying a description and JSON Schema `parameters` so the
+/// tool-calling pipeline has enough to advertise the tool to the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToolSpec {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        description: String,
+        #[serde(default)]
+        parameters: Option<serde_json::Value>,
+    },
+}
+
+impl ToolSpec {
+    /// The tool's name, regardless of which form was used.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            ToolSpec::Name(name) => name,
+            ToolSpec::Full { name, .. } => name,
+        }
+    }
+
+    /// The tool's description, empty for the bare-name form.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        match self {
+            ToolSpec::Name(_) => "",
+            ToolSpec::Full { description, .. } => description,
+        }
+    }
+
+    /// The tool's JSON Schema parameter definition, if declared.
+    #[must_use]
+    pub fn parameters(&self) -> Option<&serde_json::Value> {
+        match self {
+            ToolSpec::Name(_) => None,
+            ToolSpec::Full { parameters, .. } => parameters.as_ref(),
+        }
+    }
+}
+
+/// Declarable hook points a skill can register to act as active middleware instead of
+/// passive prompt text. Each field is a shell command invoked by the hook runner; see
+/// `moltis_skills::hooks` for the invocation contract.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillHooks {
+    /// Run before the provider stream starts; may rewrite or veto the outgoing messages.
+    #[serde(default)]
+    pub before_send: Option<String>,
+    /// Run after the accumulated text and usage are final.
+    #[serde(default)]
+    pub after_send: Option<String>,
+    /// Run when a chat send fails or the stream emits an error.
+    #[serde(default)]
+    pub on_error: Option<String>,
+}
+
+impl SkillHooks {
+    /// `true` if this skill declares no hooks at all.
+    pub fn is_empty(&self) -> bool {
+        self.before_send.is_none() && self.after_send.is_none() && self.on_error.is_none()
+    }
 }
 
 /// Full skill content: metadata + markdown body.
@@ -0,0 +1,118 @@
+use moltis_channels::membership::MemberInfo;
+use moltis_common::types::ChatType;
+
+/// Per-account access policy, parsed from the account's channel config.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct AccessConfig {
+    /// User IDs or usernames allowed to talk to the bot. Empty means "everyone".
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Chat IDs the bot will respond in. Empty means "any chat".
+    #[serde(default)]
+    pub allowed_groups: Vec<String>,
+    /// Require an @mention before replying in a group/channel.
+    #[serde(default = "default_require_mention")]
+    pub require_mention_in_groups: bool,
+    /// Restrict group/channel replies to chat admins and owners.
+    #[serde(default)]
+    pub require_admin: bool,
+    /// Restrict group/channel replies to members holding one of these role names.
+    /// Telegram has no native role concept and `membership::resolve_member` reports no
+    /// roles, so no member matches this list; it's here mainly for config-shape parity
+    /// with the Discord plugin.
+    #[serde(default)]
+    pub allowed_roles: Vec<String>,
+}
+
+fn default_require_mention() -> bool {
+    true
+}
+
+/// Decide whether an inbound message should be answered. DMs only check the user
+/// allow-list; groups/channels additionally check the group allow-list, require the
+/// bot to have been @mentioned unless disabled, and — if configured — require the
+/// sender to be a chat admin or hold an allowed role. `member` is `None` when
+/// membership couldn't be resolved (e.g. API error); admin/role gates then deny.
+pub fn check_access(
+    config: &serde_json::Value,
+    chat_type: &ChatType,
+    peer_id: &str,
+    username: Option<&str>,
+    group_id: Option<&str>,
+    bot_mentioned: bool,
+    member: Option<&MemberInfo>,
+) -> Result<(), String> {
+    let config: AccessConfig = serde_json::from_value(config.clone()).unwrap_or_default();
+
+    if !config.allowed_users.is_empty()
+        && !config
+            .allowed_users
+            .iter()
+            .any(|u| u == peer_id || Some(u.as_str()) == username)
+    {
+        return Err(format!("user '{peer_id}' is not in the allow-list"));
+    }
+
+    match chat_type {
+        ChatType::Dm => Ok(()),
+        ChatType::Group | ChatType::Channel => {
+            if !config.allowed_groups.is_empty() {
+                let allowed = group_id.map(|g| config.allowed_groups.iter().any(|a| a == g)).unwrap_or(false);
+                if !allowed {
+                    return Err(format!("chat '{}' is not in the allow-list", group_id.unwrap_or("unknown")));
+                }
+            }
+
+            if config.require_mention_in_groups && !bot_mentioned {
+                return Err("bot was not @mentioned in the chat".to_string());
+            }
+
+            if config.require_admin || !config.allowed_roles.is_empty() {
+                let Some(member) = member else {
+                    return Err("could not resolve chat membership".to_string());
+                };
+                let role_ok = !config.allowed_roles.is_empty()
+                    && member.roles.iter().any(|r| config.allowed_roles.contains(r));
+                let admin_ok = config.require_admin && member.is_admin;
+                if !(admin_ok || role_ok) {
+                    return Err(format!("user '{peer_id}' lacks the required chat role/admin status"));
+                }
+            }
+
+            Ok(())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dm_allowed_by_default() {
+        let config = serde_json::json!({});
+        assert!(check_access(&config, &ChatType::Dm, "u1", None, None, false, None).is_ok());
+    }
+
+    #[test]
+    fn group_requires_mention_by_default() {
+        let config = serde_json::json!({});
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), false, None).is_err());
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, None).is_ok());
+    }
+
+    #[test]
+    fn admin_gate_denies_non_admin() {
+        let config = serde_json::json!({ "require_admin": true });
+        let non_admin = MemberInfo { is_admin: false, roles: vec![] };
+        let admin = MemberInfo { is_admin: true, roles: vec![] };
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, Some(&non_admin)).is_err());
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, Some(&admin)).is_ok());
+    }
+
+    #[test]
+    fn admin_gate_denies_when_membership_unresolved() {
+        let config = serde_json::json!({ "require_admin": true });
+        assert!(check_access(&config, &ChatType::Group, "u1", None, Some("g1"), true, None).is_err());
+    }
+}
@@ -9,9 +9,10 @@ use {
 };
 
 use moltis_channels::message_log::MessageLogEntry;
+use moltis_channels::plugin::ChannelStreamOutbound;
 use moltis_common::types::{ChatType, MsgContext};
 
-use crate::{access, state::AccountStateMap};
+use crate::{access, membership, state::AccountStateMap};
 
 /// Shared context injected into teloxide's dispatcher.
 #[derive(Clone)]
@@ -89,6 +90,15 @@ pub async fn handle_message_direct(
 
     let username = msg.from.as_ref().and_then(|u| u.username.clone());
 
+    // Resolve the sender's chat membership (admin status) before the access check, so
+    // `require_admin`/`allowed_roles` can gate on it; skipped in DMs where it's moot.
+    let member = match (&chat_type, msg.from.as_ref()) {
+        (ChatType::Group | ChatType::Channel, Some(user)) => {
+            membership::resolve_member(bot, msg.chat.id, user.id).await
+        },
+        _ => None,
+    };
+
     // Access control
     let access_result = access::check_access(
         &config,
@@ -97,6 +107,7 @@ pub async fn handle_message_direct(
         username.as_deref(),
         group_id.as_deref(),
         bot_mentioned,
+        member.as_ref(),
     );
     let access_granted = access_result.is_ok();
 
@@ -159,16 +170,29 @@ pub async fn handle_message_direct(
         sender_name,
     };
 
-    // Dispatch to auto-reply pipeline
-    match moltis_auto_reply::reply::get_reply(&msg_ctx).await {
-        Ok(reply) => {
-            info!(account_id, to = %msg_ctx.to, text = %reply.text, "sending reply");
-            if let Err(e) = outbound.send_reply(bot, &msg_ctx.to, &reply).await {
-                warn!(account_id, "failed to send reply: {e}");
+    // Dispatch to auto-reply pipeline. Stream the reply in place via edit-in-place
+    // updates when the resolved provider supports streaming; fall back to the
+    // one-shot reply otherwise.
+    match moltis_auto_reply::reply::get_reply_stream(&msg_ctx).await {
+        Ok(stream) => {
+            let stream_outbound = crate::stream_outbound::TelegramStreamOutbound { bot: bot.clone() };
+            if let Err(e) = stream_outbound.send_stream(account_id, &msg_ctx.to, stream).await {
+                warn!(account_id, "failed to stream reply: {e}");
             }
         },
         Err(e) => {
-            warn!(account_id, "auto-reply failed: {e}");
+            debug!(account_id, error = %e, "streaming reply unavailable, falling back to one-shot");
+            match moltis_auto_reply::reply::get_reply(&msg_ctx).await {
+                Ok(reply) => {
+                    info!(account_id, to = %msg_ctx.to, text = %reply.text, "sending reply");
+                    if let Err(e) = outbound.send_reply(bot, &msg_ctx.to, &reply).await {
+                        warn!(account_id, "failed to send reply: {e}");
+                    }
+                },
+                Err(e) => {
+                    warn!(account_id, "auto-reply failed: {e}");
+                },
+            }
         },
     }
 
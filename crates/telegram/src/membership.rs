@@ -0,0 +1,45 @@
+//! Resolves a sender's admin status in a Telegram chat via `getChatMember`, cached
+//! briefly so `access::check_access`'s admin gate doesn't cost an API round-trip on
+//! every message.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use teloxide::prelude::*;
+use teloxide::types::ChatMemberStatus;
+
+use moltis_channels::membership::MemberInfo;
+
+/// How long a resolved membership stays valid before the next lookup re-fetches it.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+fn cache() -> &'static Mutex<HashMap<(ChatId, UserId), (MemberInfo, Instant)>> {
+    static CACHE: OnceLock<Mutex<HashMap<(ChatId, UserId), (MemberInfo, Instant)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Resolve `user_id`'s admin status in `chat_id`, using a cached result if it's
+/// younger than [`CACHE_TTL`]. Returns `None` if the `getChatMember` call fails (e.g.
+/// the bot was removed from the chat), leaving admin-gated access denied.
+pub async fn resolve_member(bot: &Bot, chat_id: ChatId, user_id: UserId) -> Option<MemberInfo> {
+    if let Some((info, fetched_at)) = cache().lock().unwrap().get(&(chat_id, user_id)) {
+        if fetched_at.elapsed() < CACHE_TTL {
+            return Some(info.clone());
+        }
+    }
+
+    let member = bot.get_chat_member(chat_id, user_id).await.ok()?;
+    let is_admin = matches!(
+        member.status(),
+        ChatMemberStatus::Administrator | ChatMemberStatus::Owner
+    );
+    let info = MemberInfo { is_admin, roles: Vec::new() };
+
+    cache()
+        .lock()
+        .unwrap()
+        .insert((chat_id, user_id), (info.clone(), Instant::now()));
+
+    Some(info)
+}
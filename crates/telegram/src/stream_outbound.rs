@@ -0,0 +1,89 @@
+//! Edit-in-place streaming bridge: consumes a [`StreamReceiver`] of [`StreamEvent`]s
+//! and renders it onto a single Telegram message via repeated `editMessageText`
+//! calls, throttled to respect Telegram's per-chat edit rate limit.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use teloxide::{prelude::*, types::ChatId};
+use tokio::time::Instant;
+use tracing::warn;
+
+use moltis_channels::plugin::{ChannelStreamOutbound, StreamEvent, StreamReceiver};
+
+/// Minimum gap between consecutive `editMessageText` calls against the same message,
+/// to stay well under Telegram's per-chat edit rate limit.
+const EDIT_THROTTLE: Duration = Duration::from_millis(1200);
+
+/// Telegram's hard ceiling on a single message's text length.
+const TELEGRAM_MESSAGE_LIMIT: usize = 4096;
+
+/// Streams an LLM completion into a chat as a message that's progressively edited in
+/// place, rolling over into a new message if the accumulated text would overflow
+/// Telegram's length limit. Used by [`super::handlers::handle_message_direct`] in
+/// place of a one-shot reply whenever `moltis_auto_reply::reply::get_reply_stream`
+/// resolves to a streaming-capable provider.
+pub struct TelegramStreamOutbound {
+    pub bot: Bot,
+}
+
+#[async_trait]
+impl ChannelStreamOutbound for TelegramStreamOutbound {
+    async fn send_stream(&self, _account_id: &str, to: &str, mut stream: StreamReceiver) -> anyhow::Result<()> {
+        let chat_id = ChatId(
+            to.parse::<i64>()
+                .map_err(|e| anyhow::anyhow!("invalid telegram chat id '{to}': {e}"))?,
+        );
+
+        let mut buffer = String::new();
+        let mut current = self.bot.send_message(chat_id, "…").await?;
+        let mut last_edit = Instant::now();
+        let mut dirty = false;
+
+        while let Some(event) = stream.recv().await {
+            match event {
+                StreamEvent::Delta(delta) => {
+                    if buffer.chars().count() + delta.chars().count() > TELEGRAM_MESSAGE_LIMIT {
+                        flush(&self.bot, &current, &buffer).await;
+                        buffer.clear();
+                        current = self.bot.send_message(chat_id, "…").await?;
+                        last_edit = Instant::now();
+                        dirty = false;
+                    }
+
+                    buffer.push_str(&delta);
+                    dirty = true;
+
+                    if last_edit.elapsed() >= EDIT_THROTTLE {
+                        flush(&self.bot, &current, &buffer).await;
+                        last_edit = Instant::now();
+                        dirty = false;
+                    }
+                },
+                StreamEvent::Error(e) => {
+                    buffer.push_str(&format!("\n\n⚠️ {e}"));
+                    flush(&self.bot, &current, &buffer).await;
+                    return Ok(());
+                },
+                StreamEvent::Done => {
+                    if dirty {
+                        flush(&self.bot, &current, &buffer).await;
+                    }
+                    return Ok(());
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Best-effort `editMessageText`. Telegram rejects an edit whose text is identical to
+/// the current content ("message is not modified"); that failure is logged and
+/// otherwise ignored since the next edit carries the missed content forward.
+async fn flush(bot: &Bot, message: &Message, text: &str) {
+    let text = if text.is_empty() { "…" } else { text };
+    if let Err(e) = bot.edit_message_text(message.chat.id, message.id, text).await {
+        warn!(error = %e, "failed to edit streaming message");
+    }
+}